@@ -0,0 +1,74 @@
+use nom::{
+    character::complete::{line_ending, u32},
+    multi::separated_list1,
+    IResult,
+};
+
+use aoc_common::error::{parse_all, AocError};
+use aoc_common::solution::{Answer, Solution};
+
+pub fn part_1(input: &str) -> Result<usize, AocError> {
+    let depths = parse_all(input, parse_depths)?;
+
+    Ok(count_increases(&depths))
+}
+
+pub fn part_2(input: &str) -> Result<usize, AocError> {
+    let depths = parse_all(input, parse_depths)?;
+
+    Ok(count_increases(&sum_windows(&depths)))
+}
+
+pub struct Day01 {
+    depths: Vec<u32>,
+}
+
+impl Solution for Day01 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day01 {
+            depths: parse_all(input, parse_depths)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(count_increases(&self.depths)))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(count_increases(&sum_windows(&self.depths))))
+    }
+}
+
+fn count_increases(depths: &[u32]) -> usize {
+    depths.windows(2).filter(|w| w[1] > w[0]).count()
+}
+
+fn sum_windows(depths: &[u32]) -> Vec<u32> {
+    depths.windows(3).map(|w| w.iter().sum()).collect()
+}
+
+fn parse_depths(input: &str) -> IResult<&str, Vec<u32>> {
+    separated_list1(line_ending, u32)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_sum_windows() {
+        assert_eq!(sum_windows(&[199, 200, 208, 210, 200]), vec![607, 618, 618]);
+    }
+
+    #[test]
+    fn test_part_1_with_test_input() {
+        assert_eq!(part_1(TEST_INPUT), Ok(7));
+    }
+
+    #[test]
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT), Ok(5));
+    }
+}
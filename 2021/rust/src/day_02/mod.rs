@@ -0,0 +1,121 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, u32},
+    combinator::map,
+    multi::separated_list1,
+    sequence::preceded,
+    IResult,
+};
+
+use aoc_common::error::{parse_all, AocError};
+use aoc_common::solution::{Answer, Solution};
+
+pub fn part_1(input: &str) -> Result<i64, AocError> {
+    let commands = parse_all(input, parse_commands)?;
+
+    Ok(dive(&commands))
+}
+
+pub fn part_2(input: &str) -> Result<i64, AocError> {
+    let commands = parse_all(input, parse_commands)?;
+
+    Ok(dive_with_aim(&commands))
+}
+
+pub struct Day02 {
+    commands: Vec<Command>,
+}
+
+impl Solution for Day02 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day02 {
+            commands: parse_all(input, parse_commands)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(dive(&self.commands)))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(dive_with_aim(&self.commands)))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Forward(u32),
+    Down(u32),
+    Up(u32),
+}
+
+/// Follows the commands as plain moves and multiplies the final position and depth.
+fn dive(commands: &[Command]) -> i64 {
+    let (mut position, mut depth) = (0i64, 0i64);
+
+    for command in commands {
+        match command {
+            Command::Forward(n) => position += *n as i64,
+            Command::Down(n) => depth += *n as i64,
+            Command::Up(n) => depth -= *n as i64,
+        }
+    }
+
+    position * depth
+}
+
+/// Follows the commands where up and down change the aim, and forward moves along it.
+fn dive_with_aim(commands: &[Command]) -> i64 {
+    let (mut position, mut depth, mut aim) = (0i64, 0i64, 0i64);
+
+    for command in commands {
+        match command {
+            Command::Forward(n) => {
+                position += *n as i64;
+                depth += aim * *n as i64;
+            }
+            Command::Down(n) => aim += *n as i64,
+            Command::Up(n) => aim -= *n as i64,
+        }
+    }
+
+    position * depth
+}
+
+fn parse_commands(input: &str) -> IResult<&str, Vec<Command>> {
+    separated_list1(line_ending, parse_command)(input)
+}
+
+fn parse_command(input: &str) -> IResult<&str, Command> {
+    alt((
+        map(preceded(tag("forward "), u32), Command::Forward),
+        map(preceded(tag("down "), u32), Command::Down),
+        map(preceded(tag("up "), u32), Command::Up),
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("forward 5"), Ok(("", Command::Forward(5))));
+        assert_eq!(parse_command("down 8"), Ok(("", Command::Down(8))));
+        assert_eq!(parse_command("up 3"), Ok(("", Command::Up(3))));
+        assert!(parse_command("sideways 3").is_err());
+    }
+
+    #[test]
+    fn test_part_1_with_test_input() {
+        assert_eq!(part_1(TEST_INPUT), Ok(150));
+    }
+
+    #[test]
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT), Ok(900));
+    }
+}
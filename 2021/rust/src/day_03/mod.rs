@@ -0,0 +1,197 @@
+use nom::{
+    bytes::complete::is_a, character::complete::line_ending, multi::separated_list1, IResult,
+};
+
+use aoc_common::error::{parse_all, AocError};
+use aoc_common::solution::{Answer, Solution};
+
+pub fn part_1(input: &str) -> Result<isize, AocError> {
+    let lines = parse_report(input)?;
+
+    Ok(calculate_gamma(&lines) * calculate_epsilon(&lines))
+}
+
+pub fn part_2(input: &str) -> Result<isize, AocError> {
+    let lines = parse_report(input)?;
+
+    Ok(calculate_oxygen(&lines)? * calculate_co2(&lines)?)
+}
+
+pub struct Day03 {
+    lines: Vec<String>,
+}
+
+impl Solution for Day03 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day03 {
+            lines: parse_report(input)?
+                .into_iter()
+                .map(|line| line.to_string())
+                .collect(),
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        let lines = self.lines.iter().map(|l| l.as_str()).collect::<Vec<_>>();
+
+        Ok(Box::new(
+            calculate_gamma(&lines) * calculate_epsilon(&lines),
+        ))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        let lines = self.lines.iter().map(|l| l.as_str()).collect::<Vec<_>>();
+
+        Ok(Box::new(calculate_oxygen(&lines)? * calculate_co2(&lines)?))
+    }
+}
+
+/// Reads the report as lines of bits, which must all be as wide as the first one and fit
+/// in an `isize`.
+fn parse_report(input: &str) -> Result<Vec<&str>, AocError> {
+    let lines = parse_all(input, parse_lines)?;
+
+    let width = lines[0].len();
+    if width >= isize::BITS as usize {
+        return Err(AocError::Invalid(format!(
+            "numbers of {} bits do not fit in {} bits",
+            width,
+            isize::BITS
+        )));
+    }
+
+    if let Some(i) = lines.iter().position(|line| line.len() != width) {
+        return Err(AocError::Invalid(format!(
+            "line {} has {} bits but the first line has {}",
+            i + 1,
+            lines[i].len(),
+            width
+        )));
+    }
+
+    Ok(lines)
+}
+
+fn parse_lines(input: &str) -> IResult<&str, Vec<&str>> {
+    separated_list1(line_ending, is_a("01"))(input)
+}
+
+fn find_most_common(lines: &[&str]) -> Vec<&'static str> {
+    count_bit_occurrence(lines)
+        .into_iter()
+        .map(|x| if x >= 0 { "1" } else { "0" })
+        .collect::<Vec<&str>>()
+}
+
+fn find_least_common(lines: &[&str]) -> Vec<&'static str> {
+    count_bit_occurrence(lines)
+        .into_iter()
+        .map(|x| if x >= 0 { "0" } else { "1" })
+        .collect::<Vec<&str>>()
+}
+
+fn count_bit_occurrence(lines: &[&str]) -> Vec<i32> {
+    (0..lines[0].len())
+        .map(|i| count_bit_occurrence_at(lines, i))
+        .collect()
+}
+
+/// How many more ones than zeros there are at `index`.
+fn count_bit_occurrence_at(lines: &[&str], index: usize) -> i32 {
+    lines
+        .iter()
+        .map(|line| {
+            if &line[index..index + 1] == "1" {
+                1
+            } else {
+                -1
+            }
+        })
+        .sum()
+}
+
+fn calculate_gamma(lines: &[&str]) -> isize {
+    to_number(&find_most_common(lines).concat())
+}
+
+fn calculate_epsilon(lines: &[&str]) -> isize {
+    to_number(&find_least_common(lines).concat())
+}
+
+fn calculate_oxygen(lines: &[&str]) -> Result<isize, AocError> {
+    find_rating(
+        lines,
+        |moving_count| if moving_count >= 0 { "1" } else { "0" },
+    )
+}
+
+fn calculate_co2(lines: &[&str]) -> Result<isize, AocError> {
+    find_rating(
+        lines,
+        |moving_count| if moving_count >= 0 { "0" } else { "1" },
+    )
+}
+
+/// Keeps the lines with the bit chosen by `bit_criteria` at every position, until only one
+/// line is left.
+fn find_rating<F>(lines: &[&str], bit_criteria: F) -> Result<isize, AocError>
+where
+    F: Fn(i32) -> &'static str,
+{
+    let mut candidates = lines.to_vec();
+    let total_bits = candidates[0].len();
+    for i in 0..total_bits {
+        if candidates.len() == 1 {
+            break;
+        }
+
+        let bit = bit_criteria(count_bit_occurrence_at(&candidates, i));
+        candidates.retain(|candidate| &candidate[i..i + 1] == bit);
+    }
+
+    match candidates[..] {
+        [rating] => Ok(to_number(rating)),
+        _ => Err(AocError::NoSolution(format!(
+            "{} lines match the bit criteria",
+            candidates.len()
+        ))),
+    }
+}
+
+fn to_number(bits: &str) -> isize {
+    bits.chars()
+        .fold(0, |number, bit| number * 2 + if bit == '1' { 1 } else { 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_gamma_and_epsilon() {
+        let lines = parse_report(TEST_INPUT).unwrap();
+
+        assert_eq!(calculate_gamma(&lines), 22);
+        assert_eq!(calculate_epsilon(&lines), 9);
+    }
+
+    #[test]
+    fn test_oxygen_and_co2() {
+        let lines = parse_report(TEST_INPUT).unwrap();
+
+        assert_eq!(calculate_oxygen(&lines), Ok(23));
+        assert_eq!(calculate_co2(&lines), Ok(10));
+    }
+
+    #[test]
+    fn test_part_1_with_test_input() {
+        assert_eq!(part_1(TEST_INPUT), Ok(198));
+    }
+
+    #[test]
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT), Ok(230));
+    }
+}
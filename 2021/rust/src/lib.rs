@@ -0,0 +1,31 @@
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+
+use aoc_common::solution::{solver, Solver};
+
+/// The year of the calendar the days of this crate belong to.
+pub const YEAR: u16 = 2021;
+
+pub fn get_solver(day: u8) -> Option<Solver> {
+    match day {
+        1 => Some(solver::<day_01::Day01>),
+        2 => Some(solver::<day_02::Day02>),
+        3 => Some(solver::<day_03::Day03>),
+        _ => None,
+    }
+}
+
+pub fn get_days() -> Vec<u8> {
+    (1..=25).filter(|day| get_solver(*day).is_some()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_days() {
+        assert_eq!(get_days(), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,141 @@
+use std::io::BufRead;
+
+use nom::{
+    character::complete::{line_ending, u32},
+    multi::separated_list1,
+    sequence::pair,
+    IResult,
+};
+
+use aoc_common::error::{parse_all, AocError};
+use aoc_common::input::for_each_line;
+use aoc_common::solution::{Answer, Solution, Streaming};
+
+pub fn part_1<R: BufRead>(reader: R) -> Result<u32, AocError> {
+    stream_top_calories(reader, 1)
+}
+
+pub fn part_2<R: BufRead>(reader: R) -> Result<u32, AocError> {
+    stream_top_calories(reader, 3)
+}
+
+pub struct Day01 {
+    elves: Vec<Vec<u32>>,
+}
+
+impl Solution for Day01 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day01 {
+            elves: parse_all(input, parse_elves)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(sum_top_calories(&self.elves, 1)))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(sum_top_calories(&self.elves, 3)))
+    }
+}
+
+impl Streaming for Day01 {
+    fn stream_part_one(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_1(reader)?))
+    }
+
+    fn stream_part_two(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_2(reader)?))
+    }
+}
+
+/// The calories carried by the `top` elves carrying the most, reading the elves one line at
+/// a time and only keeping the largest totals seen so far.
+fn stream_top_calories<R: BufRead>(reader: R, top: usize) -> Result<u32, AocError> {
+    let mut top_calories = Vec::with_capacity(top + 1);
+    let mut elf: Option<u32> = None;
+
+    for_each_line(reader, |i, line| {
+        if line.is_empty() {
+            if let Some(calories) = elf.take() {
+                keep_top(&mut top_calories, calories, top);
+            }
+
+            return Ok(());
+        }
+
+        let calories = parse_all(line, u32).map_err(|e| e.on_line(i))?;
+        elf = Some(elf.unwrap_or(0) + calories);
+
+        Ok(())
+    })?;
+
+    if let Some(calories) = elf {
+        keep_top(&mut top_calories, calories, top);
+    }
+
+    Ok(top_calories.iter().sum())
+}
+
+fn keep_top(top_calories: &mut Vec<u32>, calories: u32, top: usize) {
+    top_calories.push(calories);
+    top_calories.sort_unstable_by(|a, b| b.cmp(a));
+    top_calories.truncate(top);
+}
+
+/// The calories carried by the `top` elves carrying the most.
+fn sum_top_calories(elves: &[Vec<u32>], top: usize) -> u32 {
+    let mut calorie_count = elves
+        .iter()
+        .map(|calories| calories.iter().sum())
+        .collect::<Vec<u32>>();
+
+    calorie_count.sort();
+    calorie_count.iter().rev().take(top).sum()
+}
+
+/// Reads the calories of every elf, where elves are separated by an empty line.
+fn parse_elves(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
+    separated_list1(pair(line_ending, line_ending), parse_calories)(input)
+}
+
+fn parse_calories(input: &str) -> IResult<&str, Vec<u32>> {
+    separated_list1(line_ending, u32)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_parse_elves() {
+        assert_eq!(
+            parse_elves("1000\n2000\n\n4000"),
+            Ok(("", vec![vec![1000, 2000], vec![4000]]))
+        );
+    }
+
+    #[test]
+    fn test_stream_matches_parsed_elves() {
+        let elves = parse_all(TEST_INPUT, parse_elves).unwrap();
+
+        for top in 1..=5 {
+            assert_eq!(
+                stream_top_calories(TEST_INPUT.as_bytes(), top),
+                Ok(sum_top_calories(&elves, top))
+            );
+        }
+    }
+
+    #[test]
+    fn test_part_1_with_test_input() {
+        assert_eq!(part_1(TEST_INPUT.as_bytes()), Ok(24000));
+    }
+
+    #[test]
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT.as_bytes()), Ok(45000));
+    }
+}
@@ -0,0 +1,231 @@
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{char, line_ending, one_of},
+    multi::separated_list1,
+    sequence::terminated,
+    IResult,
+};
+
+use aoc_common::error::{parse_all, AocError};
+use aoc_common::solution::{Answer, Solution};
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum OpponentMove {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+impl FromStr for OpponentMove {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<OpponentMove, ()> {
+        match s {
+            "A" => Ok(OpponentMove::Rock),
+            "B" => Ok(OpponentMove::Paper),
+            "C" => Ok(OpponentMove::Scissors),
+            _ => Err(()),
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MyMove {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+impl FromStr for MyMove {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<MyMove, ()> {
+        match s {
+            "X" => Ok(MyMove::Rock),
+            "Y" => Ok(MyMove::Paper),
+            "Z" => Ok(MyMove::Scissors),
+            _ => Err(()),
+        }
+    }
+}
+
+impl MyMove {
+    fn get_outcome(&self, opponent_move: OpponentMove) -> Outcome {
+        match opponent_move {
+            OpponentMove::Rock => match self {
+                MyMove::Rock => Outcome::Draw,
+                MyMove::Paper => Outcome::Win,
+                MyMove::Scissors => Outcome::Lose,
+            },
+            OpponentMove::Paper => match self {
+                MyMove::Rock => Outcome::Lose,
+                MyMove::Paper => Outcome::Draw,
+                MyMove::Scissors => Outcome::Win,
+            },
+            OpponentMove::Scissors => match self {
+                MyMove::Rock => Outcome::Win,
+                MyMove::Paper => Outcome::Lose,
+                MyMove::Scissors => Outcome::Draw,
+            },
+        }
+    }
+
+    fn from_desired_outcome(outcome: Outcome, opponent_move: OpponentMove) -> MyMove {
+        match outcome {
+            Outcome::Lose => match opponent_move {
+                OpponentMove::Rock => MyMove::Scissors,
+                OpponentMove::Paper => MyMove::Rock,
+                OpponentMove::Scissors => MyMove::Paper,
+            },
+            Outcome::Draw => match opponent_move {
+                OpponentMove::Rock => MyMove::Rock,
+                OpponentMove::Paper => MyMove::Paper,
+                OpponentMove::Scissors => MyMove::Scissors,
+            },
+            Outcome::Win => match opponent_move {
+                OpponentMove::Rock => MyMove::Paper,
+                OpponentMove::Paper => MyMove::Scissors,
+                OpponentMove::Scissors => MyMove::Rock,
+            },
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Outcome {
+    Lose = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+impl FromStr for Outcome {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Outcome, ()> {
+        match s {
+            "X" => Ok(Outcome::Lose),
+            "Y" => Ok(Outcome::Draw),
+            "Z" => Ok(Outcome::Win),
+            _ => Err(()),
+        }
+    }
+}
+
+pub fn part_1(input: &str) -> Result<u32, AocError> {
+    let strategy_guide = parse_all(input, parse_strategy_guide)?;
+
+    Ok(score_moves(&strategy_guide))
+}
+
+pub fn part_2(input: &str) -> Result<u32, AocError> {
+    let strategy_guide = parse_all(input, parse_strategy_guide)?;
+
+    Ok(score_outcomes(&strategy_guide))
+}
+
+pub struct Day02 {
+    strategy_guide: Vec<Strategy>,
+}
+
+impl Solution for Day02 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day02 {
+            strategy_guide: parse_all(input, parse_strategy_guide)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(score_moves(&self.strategy_guide)))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(score_outcomes(&self.strategy_guide)))
+    }
+}
+
+/// One line of the strategy guide. The second column is a move in part 1 and the desired
+/// outcome in part 2, so it is read as both.
+#[derive(Debug, PartialEq, Eq)]
+struct Strategy {
+    opponent_move: OpponentMove,
+    my_move: MyMove,
+    desired_outcome: Outcome,
+}
+
+fn score_moves(strategy_guide: &[Strategy]) -> u32 {
+    strategy_guide
+        .iter()
+        .map(|strategy| {
+            let outcome = strategy.my_move.get_outcome(strategy.opponent_move);
+
+            strategy.my_move as u32 + outcome as u32
+        })
+        .sum()
+}
+
+fn score_outcomes(strategy_guide: &[Strategy]) -> u32 {
+    strategy_guide
+        .iter()
+        .map(|strategy| {
+            let my_move =
+                MyMove::from_desired_outcome(strategy.desired_outcome, strategy.opponent_move);
+
+            my_move as u32 + strategy.desired_outcome as u32
+        })
+        .sum()
+}
+
+fn parse_strategy_guide(input: &str) -> IResult<&str, Vec<Strategy>> {
+    separated_list1(line_ending, parse_strategy)(input)
+}
+
+fn parse_strategy(input: &str) -> IResult<&str, Strategy> {
+    let (input, opponent_move) = terminated(one_of("ABC"), char(' '))(input)?;
+    let (input, second_column) = one_of("XYZ")(input)?;
+
+    // both columns are limited to their letters, so they always parse
+    let strategy = Strategy {
+        opponent_move: opponent_move.to_string().parse().unwrap(),
+        my_move: second_column.to_string().parse().unwrap(),
+        desired_outcome: second_column.to_string().parse().unwrap(),
+    };
+
+    Ok((input, strategy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_parse_strategy() {
+        assert_eq!(
+            parse_strategy("A Y"),
+            Ok((
+                "",
+                Strategy {
+                    opponent_move: OpponentMove::Rock,
+                    my_move: MyMove::Paper,
+                    desired_outcome: Outcome::Draw
+                }
+            ))
+        );
+        assert!(parse_strategy("D X").is_err());
+    }
+
+    #[test]
+    fn test_part_1_with_test_input() {
+        assert_eq!(part_1(TEST_INPUT), Ok(15));
+    }
+
+    #[test]
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT), Ok(12));
+    }
+}
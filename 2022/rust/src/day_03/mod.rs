@@ -0,0 +1,212 @@
+use std::{collections::HashSet, io::BufRead};
+
+use nom::{
+    character::complete::{alpha1, line_ending},
+    multi::separated_list1,
+    IResult,
+};
+
+use aoc_common::error::{parse_all, AocError};
+use aoc_common::input::for_each_line;
+use aoc_common::solution::{Answer, Solution, Streaming};
+
+/// Sums the priorities of the items in both compartments of the rucksacks read from
+/// `reader`, one rucksack at a time.
+pub fn part_1<R: BufRead>(reader: R) -> Result<u32, AocError> {
+    let mut sum = 0;
+    for_each_line(reader, |i, line| {
+        let rucksack = parse_all(line, alpha1).map_err(|e| e.on_line(i))?;
+        sum += repeated_priority(i, rucksack)?;
+
+        Ok(())
+    })?;
+
+    Ok(sum)
+}
+
+/// Sums the priorities of the badges of the groups read from `reader`, keeping only the
+/// rucksacks of the current group.
+pub fn part_2<R: BufRead>(reader: R) -> Result<u32, AocError> {
+    let mut group: Vec<String> = Vec::with_capacity(3);
+    let mut groups = 0;
+    let mut sum = 0;
+
+    for_each_line(reader, |i, line| {
+        let rucksack = parse_all(line, alpha1).map_err(|e| e.on_line(i))?;
+        group.push(rucksack.to_string());
+
+        if group.len() == 3 {
+            groups += 1;
+            sum += badge_priority(groups, (&group[0], &group[1], &group[2]))?;
+            group.clear();
+        }
+
+        Ok(())
+    })?;
+
+    if !group.is_empty() {
+        return Err(AocError::Invalid(format!(
+            "{} rucksacks cannot be split in groups of 3",
+            groups * 3 + group.len()
+        )));
+    }
+
+    Ok(sum)
+}
+
+pub struct Day03 {
+    rucksacks: Vec<String>,
+}
+
+impl Solution for Day03 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let rucksacks = parse_all(input, parse_rucksacks)?;
+
+        Ok(Day03 {
+            rucksacks: rucksacks.into_iter().map(|r| r.to_string()).collect(),
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        let rucksacks = self
+            .rucksacks
+            .iter()
+            .map(|r| r.as_str())
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(sum_repeated_priorities(&rucksacks)?))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        let rucksacks = self
+            .rucksacks
+            .iter()
+            .map(|r| r.as_str())
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(sum_badge_priorities(&rucksacks)?))
+    }
+}
+
+impl Streaming for Day03 {
+    fn stream_part_one(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_1(reader)?))
+    }
+
+    fn stream_part_two(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_2(reader)?))
+    }
+}
+
+fn sum_repeated_priorities(lines: &[&str]) -> Result<u32, AocError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| repeated_priority(i + 1, line))
+        .sum()
+}
+
+fn repeated_priority(line_number: usize, line: &str) -> Result<u32, AocError> {
+    find_repeated_char(line)
+        .map(char_to_priority)
+        .ok_or_else(|| {
+            AocError::Invalid(format!(
+                "line {}: the compartments do not share exactly one item",
+                line_number
+            ))
+        })
+}
+
+fn find_repeated_char(line: &str) -> Option<char> {
+    if !line.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let upper: HashSet<char> = line[0..line.len() / 2].chars().collect();
+    let lower: HashSet<char> = line[line.len() / 2..line.len()].chars().collect();
+
+    upper.intersection(&lower).next().copied()
+}
+
+fn char_to_priority(c: char) -> u32 {
+    if c.is_lowercase() {
+        c as u32 - 'a' as u32 + 1
+    } else {
+        c as u32 - 'A' as u32 + 1 + 26
+    }
+}
+
+fn sum_badge_priorities(lines: &[&str]) -> Result<u32, AocError> {
+    if !lines.len().is_multiple_of(3) {
+        return Err(AocError::Invalid(format!(
+            "{} rucksacks cannot be split in groups of 3",
+            lines.len()
+        )));
+    }
+
+    lines
+        .chunks(3)
+        .enumerate()
+        .map(|(i, group)| badge_priority(i + 1, (group[0], group[1], group[2])))
+        .sum()
+}
+
+fn badge_priority(group_number: usize, group: (&str, &str, &str)) -> Result<u32, AocError> {
+    find_group_badge(group)
+        .map(char_to_priority)
+        .ok_or_else(|| AocError::Invalid(format!("group {} has no common item", group_number)))
+}
+
+fn find_group_badge(group: (&str, &str, &str)) -> Option<char> {
+    let a: HashSet<char> = group.0.chars().collect();
+    let b: HashSet<char> = group.1.chars().collect();
+    let c: HashSet<char> = group.2.chars().collect();
+
+    let intersection_of_a_and_b = a.intersection(&b).cloned().collect::<HashSet<char>>();
+    intersection_of_a_and_b.intersection(&c).next().copied()
+}
+
+fn parse_rucksacks(input: &str) -> IResult<&str, Vec<&str>> {
+    separated_list1(line_ending, alpha1)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_find_repeated_char() {
+        assert_eq!(find_repeated_char("vJrwpWtwJgWrhcsFMMfFFhFp"), Some('p'));
+        assert_eq!(find_repeated_char("abc"), None);
+    }
+
+    #[test]
+    fn test_char_to_priority() {
+        assert_eq!(char_to_priority('a'), 1);
+        assert_eq!(char_to_priority('z'), 26);
+        assert_eq!(char_to_priority('A'), 27);
+        assert_eq!(char_to_priority('Z'), 52);
+    }
+
+    #[test]
+    fn test_part_1_with_test_input() {
+        assert_eq!(part_1(TEST_INPUT.as_bytes()), Ok(157));
+    }
+
+    #[test]
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT.as_bytes()), Ok(70));
+    }
+
+    #[test]
+    fn test_part_2_with_incomplete_group() {
+        assert_eq!(
+            part_2("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL".as_bytes()),
+            Err(AocError::Invalid(
+                "2 rucksacks cannot be split in groups of 3".to_string()
+            ))
+        );
+    }
+}
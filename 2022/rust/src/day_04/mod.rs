@@ -0,0 +1,145 @@
+use std::io::BufRead;
+
+use nom::{
+    character::complete::{char, line_ending, u32},
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult,
+};
+
+use aoc_common::error::{parse_all, AocError};
+use aoc_common::input::for_each_line;
+use aoc_common::solution::{Answer, Solution, Streaming};
+use aoc_intervals::IntervalSet;
+
+type Boundaries = (u32, u32);
+
+pub fn part_1<R: BufRead>(reader: R) -> Result<usize, AocError> {
+    stream_count(reader, count_fully_overlapping)
+}
+
+pub fn part_2<R: BufRead>(reader: R) -> Result<usize, AocError> {
+    stream_count(reader, count_overlapping)
+}
+
+/// Reads the pairs one line at a time, counting those `count` counts.
+fn stream_count<R: BufRead>(
+    reader: R,
+    count: fn(&[(Boundaries, Boundaries)]) -> usize,
+) -> Result<usize, AocError> {
+    let mut total = 0;
+    for_each_line(reader, |i, line| {
+        let pair = parse_all(line, parse_pair).map_err(|e| e.on_line(i))?;
+        total += count(&[pair]);
+
+        Ok(())
+    })?;
+
+    Ok(total)
+}
+
+pub struct Day04 {
+    pairs: Vec<(Boundaries, Boundaries)>,
+}
+
+impl Solution for Day04 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day04 {
+            pairs: parse_all(input, parse_pairs)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(count_fully_overlapping(&self.pairs)))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(count_overlapping(&self.pairs)))
+    }
+}
+
+impl Streaming for Day04 {
+    fn stream_part_one(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_1(reader)?))
+    }
+
+    fn stream_part_two(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_2(reader)?))
+    }
+}
+
+fn count_fully_overlapping(pairs: &[(Boundaries, Boundaries)]) -> usize {
+    pairs
+        .iter()
+        .map(|(a, b)| {
+            (
+                create_set_from_boundaries(*a),
+                create_set_from_boundaries(*b),
+            )
+        })
+        .filter(|(set_a, set_b)| set_a.is_subset(set_b) || set_b.is_subset(set_a))
+        .count()
+}
+
+fn count_overlapping(pairs: &[(Boundaries, Boundaries)]) -> usize {
+    pairs
+        .iter()
+        .map(|(a, b)| {
+            (
+                create_set_from_boundaries(*a),
+                create_set_from_boundaries(*b),
+            )
+        })
+        .filter(|(set_a, set_b)| set_a.overlaps(set_b))
+        .count()
+}
+
+/// The sections from `start` to `end`, both included.
+fn create_set_from_boundaries((start, end): Boundaries) -> IntervalSet<u64> {
+    IntervalSet::from(start as u64..end as u64 + 1)
+}
+
+fn parse_pairs(input: &str) -> IResult<&str, Vec<(Boundaries, Boundaries)>> {
+    separated_list1(line_ending, parse_pair)(input)
+}
+
+fn parse_pair(input: &str) -> IResult<&str, (Boundaries, Boundaries)> {
+    separated_pair(parse_boundaries, char(','), parse_boundaries)(input)
+}
+
+fn parse_boundaries(input: &str) -> IResult<&str, Boundaries> {
+    separated_pair(u32, char('-'), u32)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_parse_pairs() {
+        assert_eq!(
+            parse_pairs("2-4,6-8\n2-3,4-5"),
+            Ok(("", vec![((2, 4), (6, 8)), ((2, 3), (4, 5))]))
+        );
+    }
+
+    #[test]
+    fn test_sections_far_apart() {
+        let input = "1-4294967295,5-6\n1-2,4294967295-4294967295";
+
+        assert_eq!(part_1(input.as_bytes()), Ok(1));
+        assert_eq!(part_2(input.as_bytes()), Ok(1));
+    }
+
+    #[test]
+    fn test_part_1_with_test_input() {
+        assert_eq!(part_1(TEST_INPUT.as_bytes()), Ok(2));
+    }
+
+    #[test]
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT.as_bytes()), Ok(4));
+    }
+}
@@ -0,0 +1,324 @@
+use std::{collections::HashMap, str::FromStr};
+
+use nom::{
+    bytes::complete::{tag, take},
+    character::complete::u32,
+    error::Error,
+    sequence::{delimited, preceded, tuple},
+    IResult,
+};
+
+use aoc_common::error::{parse_all, AocError};
+use aoc_common::solution::{Answer, Solution};
+
+pub fn part_1(input: &str) -> Result<String, AocError> {
+    let (stacks, moves) = parse_input(input)?;
+
+    let mut crane = Crane::new(stacks);
+    crane.apply_9k_moves(&moves)?;
+
+    Ok(crane.peek_stacks())
+}
+
+pub fn part_2(input: &str) -> Result<String, AocError> {
+    let (stacks, moves) = parse_input(input)?;
+
+    let mut crane = Crane::new(stacks);
+    crane.apply_9k1_moves(&moves)?;
+
+    Ok(crane.peek_stacks())
+}
+
+pub struct Day05 {
+    stacks: Vec<CrateStack>,
+    moves: Vec<Move>,
+}
+
+impl Solution for Day05 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let (stacks, moves) = parse_input(input)?;
+
+        Ok(Day05 { stacks, moves })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        let mut crane = Crane::new(self.stacks.clone());
+        crane.apply_9k_moves(&self.moves)?;
+
+        Ok(Box::new(crane.peek_stacks()))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        let mut crane = Crane::new(self.stacks.clone());
+        crane.apply_9k1_moves(&self.moves)?;
+
+        Ok(Box::new(crane.peek_stacks()))
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Vec<CrateStack>, Vec<Move>), AocError> {
+    let crates_section = get_crates_section(input);
+    let Some((stack_ids, crate_rows)) = crates_section.split_last() else {
+        return Err(AocError::Invalid("there are no stacks".to_string()));
+    };
+
+    let crate_rows: Vec<Vec<Option<Crate>>> = crate_rows
+        .iter()
+        .map(|line| Crate::parse_crate_row(line))
+        .collect();
+
+    // the moves start after the crates and the empty line that follows them
+    let first_move_line = crates_section.len() + 2;
+    let moves = get_moves_section(input)
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse::<Move>()
+                .map_err(|e| e.on_line(first_move_line + i))
+        })
+        .collect::<Result<Vec<Move>, AocError>>()?;
+
+    let mut stacks: Vec<CrateStack> = (1..=stack_ids.split_whitespace().count())
+        .map(CrateStack::new)
+        .collect();
+
+    for stack in &mut stacks {
+        for row in crate_rows.iter().rev() {
+            if let Some(Some(crate_)) = row.get(stack.id - 1) {
+                stack.push(crate_.clone());
+            }
+        }
+    }
+
+    Ok((stacks, moves))
+}
+
+#[derive(Debug, Clone)]
+struct Crate {
+    name: String,
+}
+
+impl Crate {
+    fn parse_crate_row(line: &str) -> Vec<Option<Self>> {
+        line.chars()
+            .collect::<Vec<char>>()
+            .chunks(4)
+            .map(|maybe_crate| {
+                let maybe_crate = maybe_crate.iter().collect::<String>();
+                maybe_crate.trim_end().parse::<Crate>().ok()
+            })
+            .collect::<Vec<Option<Crate>>>()
+    }
+}
+
+impl FromStr for Crate {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = delimited::<_, _, _, _, Error<_>, _, _, _>(tag("["), take(1u8), tag("]"));
+        let (_, name) = parser(s).map_err(|_| ())?;
+
+        Ok(Crate {
+            name: name.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Move {
+    amount: usize,
+    from: usize,
+    to: usize,
+}
+
+impl FromStr for Move {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s, parse_move)
+    }
+}
+
+fn parse_move(input: &str) -> IResult<&str, Move> {
+    let (input, (amount, from, to)) = tuple((
+        preceded(tag("move "), u32),
+        preceded(tag(" from "), u32),
+        preceded(tag(" to "), u32),
+    ))(input)?;
+
+    let move_ = Move {
+        amount: amount as usize,
+        from: from as usize,
+        to: to as usize,
+    };
+
+    Ok((input, move_))
+}
+
+#[derive(Debug, Clone)]
+struct CrateStack {
+    id: usize,
+    crates: Vec<Crate>,
+}
+
+impl CrateStack {
+    fn new(id: usize) -> Self {
+        Self { id, crates: vec![] }
+    }
+
+    fn push(&mut self, crate_: Crate) {
+        self.crates.push(crate_);
+    }
+
+    fn pop(&mut self) -> Option<Crate> {
+        self.crates.pop()
+    }
+
+    fn peek(&self) -> Option<&Crate> {
+        self.crates.last()
+    }
+}
+
+#[derive(Debug)]
+struct Crane {
+    stacks: HashMap<usize, CrateStack>,
+}
+
+impl Crane {
+    fn new(stacks: Vec<CrateStack>) -> Self {
+        let stacks = stacks
+            .into_iter()
+            .map(|stack| (stack.id, stack))
+            .collect::<HashMap<usize, CrateStack>>();
+
+        Self { stacks }
+    }
+
+    /// Takes the crates of a move off its `from` stack, topmost first.
+    fn lift(&mut self, move_: &Move) -> Result<Vec<Crate>, AocError> {
+        let from = self.get_stack(move_.from)?;
+
+        let mut crates = vec![];
+        for _ in 0..move_.amount {
+            let Some(crt) = from.pop() else {
+                continue;
+            };
+            crates.push(crt);
+        }
+
+        Ok(crates)
+    }
+
+    fn get_stack(&mut self, id: usize) -> Result<&mut CrateStack, AocError> {
+        self.stacks
+            .get_mut(&id)
+            .ok_or_else(|| AocError::Invalid(format!("there is no stack {}", id)))
+    }
+
+    fn apply_9k_move(&mut self, move_: &Move) -> Result<(), AocError> {
+        let crates = self.lift(move_)?;
+
+        let to = self.get_stack(move_.to)?;
+        for crate_ in crates.into_iter() {
+            to.push(crate_);
+        }
+
+        Ok(())
+    }
+
+    fn apply_9k_moves(&mut self, moves: &[Move]) -> Result<(), AocError> {
+        moves.iter().try_for_each(|move_| self.apply_9k_move(move_))
+    }
+
+    fn apply_9k1_move(&mut self, move_: &Move) -> Result<(), AocError> {
+        let crates = self.lift(move_)?;
+
+        let to = self.get_stack(move_.to)?;
+        for crate_ in crates.into_iter().rev() {
+            to.push(crate_);
+        }
+
+        Ok(())
+    }
+
+    fn apply_9k1_moves(&mut self, moves: &[Move]) -> Result<(), AocError> {
+        moves
+            .iter()
+            .try_for_each(|move_| self.apply_9k1_move(move_))
+    }
+
+    fn peek_stacks(&self) -> String {
+        let mut sorted_stacks = self.stacks.values().collect::<Vec<&CrateStack>>();
+        sorted_stacks.sort_by_key(|stack| stack.id);
+
+        sorted_stacks
+            .iter()
+            .filter_map(|stack| stack.peek())
+            .map(|crate_| crate_.name.clone())
+            .collect::<_>()
+    }
+}
+
+fn get_crates_section(lines: &str) -> Vec<&str> {
+    lines.lines().take_while(|line| !line.is_empty()).collect()
+}
+
+fn get_moves_section(lines: &str) -> Vec<&str> {
+    lines
+        .lines()
+        .skip_while(|line| !line.is_empty())
+        .skip(1)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::ErrorKind;
+
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_parse_crate_row() {
+        let row = Crate::parse_crate_row("[N] [C]    ");
+
+        assert_eq!(
+            row.iter()
+                .map(|c| c.as_ref().map(|c| c.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![Some("N"), Some("C"), None]
+        );
+    }
+
+    #[test]
+    fn test_parse_move() {
+        assert_eq!(
+            "move 3 from 1 to 3".parse::<Move>(),
+            Ok(Move {
+                amount: 3,
+                from: 1,
+                to: 3
+            })
+        );
+        assert_eq!(
+            "move 3 from 1 onto 3".parse::<Move>(),
+            Err(AocError::Parse {
+                line: 1,
+                column: 14,
+                kind: ErrorKind::Tag
+            })
+        );
+    }
+
+    #[test]
+    fn test_part_1_with_test_input() {
+        assert_eq!(part_1(TEST_INPUT), Ok("CMZ".to_string()));
+    }
+
+    #[test]
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT), Ok("MCD".to_string()));
+    }
+}
@@ -0,0 +1,45 @@
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+
+use aoc_common::solution::{solver, streamer, Solver, Streamer};
+
+/// The year of the calendar the days of this crate belong to.
+pub const YEAR: u16 = 2022;
+
+pub fn get_solver(day: u8) -> Option<Solver> {
+    match day {
+        1 => Some(solver::<day_01::Day01>),
+        2 => Some(solver::<day_02::Day02>),
+        3 => Some(solver::<day_03::Day03>),
+        4 => Some(solver::<day_04::Day04>),
+        5 => Some(solver::<day_05::Day05>),
+        _ => None,
+    }
+}
+
+pub fn get_days() -> Vec<u8> {
+    (1..=25).filter(|day| get_solver(*day).is_some()).collect()
+}
+
+/// The days that can also be solved one line at a time.
+pub fn get_streamer(day: u8) -> Option<Streamer> {
+    match day {
+        1 => Some(streamer::<day_01::Day01>),
+        3 => Some(streamer::<day_03::Day03>),
+        4 => Some(streamer::<day_04::Day04>),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_days() {
+        assert_eq!(get_days(), (1..=5).collect::<Vec<u8>>());
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use aoc_2023_rust::{generate::generate, get_solver};
+use criterion::{black_box, Criterion};
+
+/// The complete input of every day, which is what the benchmarks run on.
+const INPUTS: [(u8, &str); 11] = [
+    (1, include_str!("../src/day_01/input.txt")),
+    (2, include_str!("../src/day_02/input-01.txt")),
+    (3, include_str!("../src/day_03/input-01.txt")),
+    (4, include_str!("../src/day_04/input-01.txt")),
+    (5, include_str!("../src/day_05/input.txt")),
+    (6, include_str!("../src/day_06/complete_input.txt")),
+    (7, include_str!("../src/day_07/complete_input.txt")),
+    (8, include_str!("../src/day_08/complete_input.txt")),
+    (9, include_str!("../src/day_09/complete_input.txt")),
+    (10, include_str!("../src/day_10/complete-input.txt")),
+    (11, include_str!("../src/day_11/complete-input.txt")),
+];
+
+const BENCHMARKS: [&str; 3] = ["parse", "part_1", "part_2"];
+
+/// Generated inputs far larger than the complete ones, as the day, seed and size to generate.
+const GENERATED_INPUTS: [(u8, u64, usize); 1] = [(3, 0, 10_000)];
+
+/// How many days the summary lists.
+const SLOWEST_DAYS: usize = 5;
+
+fn bench_days(c: &mut Criterion) {
+    for (day, input) in INPUTS {
+        let solver = get_solver(day).unwrap_or_else(|| panic!("day {} is not registered", day));
+        let solution = solver(input).unwrap_or_else(|e| panic!("day {}: {}", day, e));
+
+        let mut group = c.benchmark_group(group_name(day));
+        group.bench_function("parse", |b| b.iter(|| solver(black_box(input))));
+        group.bench_function("part_1", |b| b.iter(|| solution.part_one()));
+        group.bench_function("part_2", |b| b.iter(|| solution.part_two()));
+        group.finish();
+    }
+}
+
+/// Every generated input is solved a handful of times only, as a single run takes seconds.
+fn bench_generated_inputs(c: &mut Criterion) {
+    for (day, seed, size) in GENERATED_INPUTS {
+        let input = generate(day, seed, size).unwrap_or_else(|e| panic!("day {}: {}", day, e));
+        let solver = get_solver(day).unwrap_or_else(|| panic!("day {} is not registered", day));
+
+        let mut group = c.benchmark_group(format!("{}_size_{}", group_name(day), size));
+        group.sample_size(10);
+        group.bench_function("solve", |b| {
+            b.iter(|| {
+                let solution = solver(black_box(&input)).unwrap();
+                (solution.part_one(), solution.part_two())
+            })
+        });
+        group.finish();
+    }
+}
+
+fn group_name(day: u8) -> String {
+    format!("day_{:02}", day)
+}
+
+/// Lists the days that take the longest to parse and solve both parts, from the estimates
+/// criterion leaves behind. Days filtered out of this run keep their previous estimates.
+fn print_summary() {
+    let criterion_dir = target_dir().join("criterion");
+
+    let mut days = INPUTS
+        .iter()
+        .filter_map(|(day, _)| {
+            let times = BENCHMARKS
+                .iter()
+                .map(|bench| read_mean(&criterion_dir.join(group_name(*day)).join(bench)))
+                .collect::<Option<Vec<_>>>()?;
+
+            Some((*day, times))
+        })
+        .collect::<Vec<_>>();
+
+    if days.is_empty() {
+        return;
+    }
+
+    days.sort_by_key(|(_, times)| std::cmp::Reverse(times.iter().sum::<Duration>()));
+
+    println!();
+    println!("slowest days (mean of parse + part 1 + part 2):");
+    println!(
+        "{:>6} {:>12} {:>12} {:>12} {:>12}",
+        "day", "parse", "part 1", "part 2", "total"
+    );
+    for (day, times) in days.iter().take(SLOWEST_DAYS) {
+        println!(
+            "{:>6} {:>12} {:>12} {:>12} {:>12}",
+            day,
+            format_duration(times[0]),
+            format_duration(times[1]),
+            format_duration(times[2]),
+            format_duration(times.iter().sum())
+        );
+    }
+}
+
+/// Reads the mean time of a benchmark from the `new/estimates.json` criterion writes.
+fn read_mean(bench_dir: &Path) -> Option<Duration> {
+    let estimates = fs::read_to_string(bench_dir.join("new").join("estimates.json")).ok()?;
+    let estimates: serde_json::Value = serde_json::from_str(&estimates).ok()?;
+    let nanos = estimates["mean"]["point_estimate"].as_f64()?;
+
+    Some(Duration::from_nanos(nanos as u64))
+}
+
+/// Finds the target directory the same way criterion does, so the summary reads the
+/// estimates of this run even when the crate is built as part of a workspace.
+fn target_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("CARGO_TARGET_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    Command::new(cargo)
+        .args(["metadata", "--format-version", "1", "--no-deps"])
+        .output()
+        .ok()
+        .and_then(|output| serde_json::from_slice::<serde_json::Value>(&output.stdout).ok())
+        .and_then(|metadata| metadata["target_directory"].as_str().map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from("target"))
+}
+
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1_000_000.0;
+
+    if micros < 1_000.0 {
+        format!("{:.1}µs", micros)
+    } else if micros < 1_000_000.0 {
+        format!("{:.3}ms", micros / 1_000.0)
+    } else {
+        format!("{:.3}s", micros / 1_000_000.0)
+    }
+}
+
+fn main() {
+    let mut criterion = Criterion::default().configure_from_args();
+
+    bench_days(&mut criterion);
+    bench_generated_inputs(&mut criterion);
+    criterion.final_summary();
+
+    print_summary();
+}
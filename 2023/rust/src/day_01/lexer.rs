@@ -0,0 +1,235 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::error::AocError;
+
+/// A word found in a line, between the byte positions `start` and `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+/// Finds the words of a table in a line, overlapping ones included, in a single pass.
+///
+/// The words are put in a trie whose nodes also know where to carry on when the next byte
+/// does not follow any word, the longest suffix of what was read that is still the prefix of
+/// a word (Aho–Corasick). Both are then compiled into a table with the next state for every
+/// state and byte, so reading a line is a lookup per byte.
+#[derive(Debug, Clone)]
+pub struct DigitLexer {
+    transitions: Vec<[usize; 256]>,
+    /// The length and value of every word that ends at each state.
+    outputs: Vec<Vec<(usize, u32)>>,
+}
+
+impl DigitLexer {
+    /// Builds a lexer for the words of a word-to-value table, which can neither be empty nor
+    /// be given two different values.
+    pub fn new<'a, I>(words: I) -> Result<Self, AocError>
+    where
+        I: IntoIterator<Item = (&'a str, u32)>,
+    {
+        let mut children = vec![HashMap::new()];
+        let mut outputs = vec![vec![]];
+        let mut values = HashMap::new();
+
+        for (word, value) in words {
+            if word.is_empty() {
+                return Err(AocError::Invalid(
+                    "the lexer cannot match an empty word".to_string(),
+                ));
+            }
+
+            match values.insert(word, value) {
+                Some(previous) if previous != value => {
+                    return Err(AocError::Invalid(format!(
+                        "{} is both {} and {}",
+                        word, previous, value
+                    )))
+                }
+                Some(_) => continue,
+                None => {}
+            }
+
+            let mut state = 0;
+            for &byte in word.as_bytes() {
+                state = match children[state].get(&byte) {
+                    Some(&child) => child,
+                    None => {
+                        let child = children.len();
+                        children.push(HashMap::new());
+                        outputs.push(vec![]);
+                        children[state].insert(byte, child);
+
+                        child
+                    }
+                };
+            }
+            outputs[state].push((word.len(), value));
+        }
+
+        // states are visited by depth, so the state a failure leads to is always complete
+        let mut transitions = vec![[0; 256]; children.len()];
+        let mut failures = vec![0; children.len()];
+        let mut queue = VecDeque::from([0]);
+
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..=255u8 {
+                let fallback = transitions[failures[state]][byte as usize];
+
+                transitions[state][byte as usize] = match children[state].get(&byte) {
+                    Some(&child) => {
+                        failures[child] = if state == 0 { 0 } else { fallback };
+                        let inherited = outputs[failures[child]].clone();
+                        outputs[child].extend(inherited);
+                        queue.push_back(child);
+
+                        child
+                    }
+                    None => fallback,
+                };
+            }
+        }
+
+        Ok(DigitLexer {
+            transitions,
+            outputs,
+        })
+    }
+
+    /// Every word in `line`, in the order in which they end.
+    pub fn all_matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Match> + 'a {
+        line.bytes()
+            .enumerate()
+            .scan(0, |state, (i, byte)| {
+                *state = self.transitions[*state][byte as usize];
+
+                Some((i + 1, *state))
+            })
+            .flat_map(|(end, state)| {
+                self.outputs[state].iter().map(move |&(len, value)| Match {
+                    start: end - len,
+                    end,
+                    value,
+                })
+            })
+    }
+
+    /// The word that starts first in `line`, the longest one if several do.
+    pub fn first(&self, line: &str) -> Option<Match> {
+        self.all_matches(line)
+            .min_by_key(|m| (m.start, usize::MAX - m.end))
+    }
+
+    /// The word that ends last in `line`, the longest one if several do.
+    pub fn last(&self, line: &str) -> Option<Match> {
+        self.all_matches(line)
+            .max_by_key(|m| (m.end, usize::MAX - m.start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_01::Vocabulary;
+
+    fn values(matches: impl Iterator<Item = Match>) -> Vec<u32> {
+        matches.map(|m| m.value).collect()
+    }
+
+    #[test]
+    fn test_all_matches() {
+        let lexer = Vocabulary::english().lexer().unwrap();
+
+        assert_eq!(values(lexer.all_matches("xtwone3four")), vec![2, 1, 3, 4]);
+        assert_eq!(
+            lexer.all_matches("oneight").collect::<Vec<_>>(),
+            vec![
+                Match {
+                    start: 0,
+                    end: 3,
+                    value: 1
+                },
+                Match {
+                    start: 2,
+                    end: 7,
+                    value: 8
+                }
+            ]
+        );
+        assert_eq!(values(lexer.all_matches("sevenine")), vec![7, 9]);
+        assert_eq!(values(lexer.all_matches("abc")), vec![]);
+        assert_eq!(
+            values(
+                Vocabulary::default()
+                    .lexer()
+                    .unwrap()
+                    .all_matches("two1nine")
+            ),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_first_and_last() {
+        let lexer = Vocabulary::english().lexer().unwrap();
+
+        assert_eq!(lexer.first("zoneight234").map(|m| m.value), Some(1));
+        assert_eq!(lexer.last("zoneight234").map(|m| m.value), Some(4));
+        assert_eq!(
+            lexer.last("7pqrstsixteen"),
+            Some(Match {
+                start: 6,
+                end: 9,
+                value: 6
+            })
+        );
+        assert_eq!(lexer.first("treb"), None);
+        assert_eq!(lexer.last("treb"), None);
+    }
+
+    #[test]
+    fn test_overlapping_words_of_a_table() {
+        let lexer = DigitLexer::new([("he", 1), ("she", 2), ("his", 3), ("hers", 4)]).unwrap();
+
+        assert_eq!(values(lexer.all_matches("ushers")), vec![2, 1, 4]);
+        assert_eq!(lexer.first("ushers").map(|m| m.start), Some(1));
+        assert_eq!(lexer.last("ushers").map(|m| m.value), Some(4));
+        assert_eq!(lexer.last("hishe").map(|m| m.value), Some(2));
+    }
+
+    #[test]
+    fn test_byte_positions() {
+        let lexer = DigitLexer::new([("un", 1), ("deux", 2), ("trois", 3), ("dès", 4)]).unwrap();
+
+        assert_eq!(
+            lexer.all_matches("ça dès deux").collect::<Vec<_>>(),
+            vec![
+                Match {
+                    start: 4,
+                    end: 8,
+                    value: 4
+                },
+                Match {
+                    start: 9,
+                    end: 13,
+                    value: 2
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_tables() {
+        assert!(matches!(
+            DigitLexer::new([("one", 1), ("", 0)]),
+            Err(AocError::Invalid(_))
+        ));
+        assert!(matches!(
+            DigitLexer::new([("one", 1), ("one", 2)]),
+            Err(AocError::Invalid(_))
+        ));
+        assert!(DigitLexer::new([("one", 1), ("one", 1)]).is_ok());
+    }
+}
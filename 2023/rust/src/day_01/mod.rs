@@ -1,132 +1,174 @@
-pub fn part_01(lines: &Vec<&str>) -> Vec<u32> {
-    lines
-        .iter()
-        .map(|line| {
-            let mut numbers = line.chars().filter(|c| c.is_ascii_digit());
-            let first = numbers.next().unwrap();
-            let last = match numbers.last() {
-                Some(n) => n,
-                None => first,
-            };
-
-            let number = format!("{}{}", first, last);
-            number.parse::<u32>().unwrap()
-        })
-        .collect()
+mod lexer;
+mod vocabulary;
+
+use std::io::BufRead;
+
+use nom::error::ErrorKind;
+
+pub use lexer::{DigitLexer, Match};
+pub use vocabulary::Vocabulary;
+
+use crate::error::AocError;
+use crate::input::for_each_line;
+use crate::solution::{Answer, Solution, Streaming};
+
+/// Sums the calibration values of the lines read from `reader`, one line at a time.
+pub fn part_01<R: BufRead>(reader: R) -> Result<u32, AocError> {
+    sum_calibration_values(reader, |line| {
+        let mut numbers = line.chars().filter_map(|c| c.to_digit(10));
+        let first = numbers.next()?;
+
+        Some((first, numbers.next_back().unwrap_or(first)))
+    })
 }
 
-pub fn part_02(lines: &Vec<&str>) -> Vec<u32> {
-    lines
-        .iter()
-        .map(|line| {
-            let first = get_first_associated_number(line).unwrap();
-            let last = match get_last_associated_number(line) {
-                Some(n) => n,
-                None => first,
-            };
-
-            format!("{}{}", first, last).parse::<u32>().unwrap()
-        })
-        .collect()
+/// Sums the calibration values of the lines read from `reader`, where digits may also be
+/// spelled out.
+pub fn part_02<R: BufRead>(reader: R) -> Result<u32, AocError> {
+    calibrate(reader, &Vocabulary::english())
 }
 
-fn get_first_associated_number(line: &str) -> Option<u32> {
-    for (i, c) in line.char_indices() {
-        let s = &line[i..];
-
-        if c.is_ascii_digit() {
-            return c.to_digit(10);
-        } else if s.starts_with("one") {
-            return Some(1);
-        } else if s.starts_with("two") {
-            return Some(2);
-        } else if s.starts_with("three") {
-            return Some(3);
-        } else if s.starts_with("four") {
-            return Some(4);
-        } else if s.starts_with("five") {
-            return Some(5);
-        } else if s.starts_with("six") {
-            return Some(6);
-        } else if s.starts_with("seven") {
-            return Some(7);
-        } else if s.starts_with("eight") {
-            return Some(8);
-        } else if s.starts_with("nine") {
-            return Some(9);
+/// Sums the calibration values of the lines read from `reader`, where the words of the
+/// vocabulary count as numbers too. A line begins with the first digit of the number that
+/// starts first and ends with the last digit of the number that ends last.
+pub fn calibrate<R: BufRead>(reader: R, vocabulary: &Vocabulary) -> Result<u32, AocError> {
+    let lexer = vocabulary.lexer()?;
+
+    sum_calibration_values(reader, |line| {
+        let mut first = lexer.first(line)?.value;
+        while first >= 10 {
+            first /= 10;
         }
+
+        Some((first, lexer.last(line)?.value % 10))
+    })
+}
+
+fn sum_calibration_values<R, F>(reader: R, first_and_last: F) -> Result<u32, AocError>
+where
+    R: BufRead,
+    F: Fn(&str) -> Option<(u32, u32)>,
+{
+    let mut sum = 0;
+    for_each_line(reader, |i, line| {
+        let (first, last) = first_and_last(line).ok_or_else(|| missing_digit(i, line))?;
+        sum += first * 10 + last;
+
+        Ok(())
+    })?;
+
+    Ok(sum)
+}
+
+fn missing_digit(line_number: usize, line: &str) -> AocError {
+    AocError::Parse {
+        line: line_number,
+        column: line.chars().count() + 1,
+        kind: ErrorKind::Digit,
     }
+}
 
-    None
+pub struct Day01 {
+    input: String,
 }
 
-fn get_last_associated_number(line: &str) -> Option<u32> {
-    for (i, c) in line.char_indices().rev() {
-        let s = &line[..=i];
-
-        if c.is_ascii_digit() {
-            return c.to_digit(10);
-        } else if s.ends_with("one") {
-            return Some(1);
-        } else if s.ends_with("two") {
-            return Some(2);
-        } else if s.ends_with("three") {
-            return Some(3);
-        } else if s.ends_with("four") {
-            return Some(4);
-        } else if s.ends_with("five") {
-            return Some(5);
-        } else if s.ends_with("six") {
-            return Some(6);
-        } else if s.ends_with("seven") {
-            return Some(7);
-        } else if s.ends_with("eight") {
-            return Some(8);
-        } else if s.ends_with("nine") {
-            return Some(9);
-        }
+impl Solution for Day01 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day01 {
+            input: input.to_string(),
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(part_01(self.input.as_bytes())?))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(part_02(self.input.as_bytes())?))
+    }
+}
+
+impl Streaming for Day01 {
+    fn stream_part_one(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_01(reader)?))
     }
 
-    None
+    fn stream_part_two(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_02(reader)?))
+    }
 }
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use std::fs;
+    use std::{fs::File, io::BufReader};
+
     #[test]
     fn test_part_01_with_test_input() {
-        let lines = fs::read_to_string("src/day_01/test-input-01.txt").unwrap();
-        let lines = lines.lines().collect();
-        let numbers = part_01(&lines);
+        let reader = BufReader::new(File::open("src/day_01/test-input-01.txt").unwrap());
 
-        assert_eq!(numbers.into_iter().sum::<u32>(), 142);
+        assert_eq!(part_01(reader), Ok(142));
     }
 
     #[test]
-    fn test_part_01_with_real_input() {
-        let lines = fs::read_to_string("src/day_01/input.txt").unwrap();
-        let lines = lines.lines().collect();
-        let numbers = part_01(&lines);
+    fn test_part_02_with_test_input() {
+        let reader = BufReader::new(File::open("src/day_01/test-input-02.txt").unwrap());
 
-        assert_eq!(numbers.into_iter().sum::<u32>(), 54667);
+        assert_eq!(part_02(reader), Ok(281));
     }
 
     #[test]
-    fn test_part_02_with_test_input() {
-        let lines = fs::read_to_string("src/day_01/test-input-02.txt").unwrap();
-        let lines = lines.lines().collect();
-        let numbers = part_02(&lines);
-
-        assert_eq!(numbers.into_iter().sum::<u32>(), 281);
+    fn test_calibrate_with_other_vocabularies() {
+        let calibrate_line =
+            |line: &str, vocabulary: &Vocabulary| calibrate(line.as_bytes(), vocabulary);
+
+        assert_eq!(calibrate_line("xdeuxzéro7", &Vocabulary::french()), Ok(27));
+        assert_eq!(
+            calibrate_line("fünfundzwanzig", &Vocabulary::german()),
+            Ok(55)
+        );
+        assert_eq!(calibrate_line("unodostres", &Vocabulary::spanish()), Ok(13));
+        assert_eq!(
+            calibrate_line("zeroabc", &Vocabulary::english()),
+            Err(missing_digit(1, "zeroabc"))
+        );
+        assert_eq!(
+            calibrate_line("zero2", &Vocabulary::english_numerals()),
+            Ok(2)
+        );
+        assert_eq!(
+            calibrate_line("twenty-one", &Vocabulary::english_numerals()),
+            Ok(21)
+        );
+        assert_eq!(
+            calibrate_line("seventeenxfortytwo", &Vocabulary::english_numerals()),
+            Ok(12)
+        );
+        assert_eq!(calibrate_line("aXIVb3", &Vocabulary::roman()), Ok(13));
+        assert_eq!(calibrate_line("3VIII", &Vocabulary::roman()), Ok(38));
+        assert_eq!(
+            calibrate_line(
+                "uno3six",
+                &Vocabulary::spanish().with(Vocabulary::english())
+            ),
+            Ok(16)
+        );
+        assert_eq!(
+            calibrate("two\nfour".as_bytes(), &"two 20\nfour 41".parse().unwrap()),
+            Ok(20 + 41)
+        );
     }
 
     #[test]
-    fn test_part_02_with_real_input() {
-        let lines = fs::read_to_string("src/day_01/input.txt").unwrap();
-        let lines = lines.lines().collect();
-        let numbers = part_02(&lines);
-
-        assert_eq!(numbers.into_iter().sum::<u32>(), 54203);
+    fn test_line_without_digits() {
+        assert_eq!(
+            part_01("1abc2\npqrstu".as_bytes()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 7,
+                kind: ErrorKind::Digit
+            })
+        );
     }
 }
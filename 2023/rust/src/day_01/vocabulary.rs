@@ -0,0 +1,272 @@
+use std::{collections::HashMap, fs, path::Path, str::FromStr};
+
+use nom::{
+    bytes::complete::take_till1,
+    character::complete::{space0, space1, u32},
+    sequence::{terminated, tuple},
+    IResult,
+};
+
+use crate::error::{parse_all, AocError};
+
+use super::lexer::DigitLexer;
+
+const ENGLISH: &str = include_str!("vocabularies/english.txt");
+const FRENCH: &str = include_str!("vocabularies/french.txt");
+const GERMAN: &str = include_str!("vocabularies/german.txt");
+const SPANISH: &str = include_str!("vocabularies/spanish.txt");
+
+const UNITS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// The words that may stand for numbers in a calibration document, besides the digits,
+/// which always do.
+///
+/// A vocabulary is kept as a table of one `word value` entry per line, where `#` starts a
+/// comment line. Words cannot hold whitespace, but they can be numerals of several digits:
+/// a line then begins with the first digit of the number and ends with its last one.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// "one" to "nine", as in the puzzle.
+    pub fn english() -> Self {
+        ENGLISH.parse().unwrap()
+    }
+
+    /// "zero" to "ninety-nine".
+    pub fn english_numerals() -> Self {
+        let units = UNITS
+            .iter()
+            .zip(0..)
+            .map(|(&word, value)| (word.to_string(), value));
+        let tens = TENS
+            .iter()
+            .zip((20..).step_by(10))
+            .flat_map(|(&ten, value)| {
+                let compounds = UNITS[1..10]
+                    .iter()
+                    .zip(1..)
+                    .map(move |(unit, n)| (format!("{}-{}", ten, unit), value + n));
+
+                [(ten.to_string(), value)].into_iter().chain(compounds)
+            });
+
+        Vocabulary {
+            words: units.chain(tens).collect(),
+        }
+    }
+
+    pub fn french() -> Self {
+        FRENCH.parse().unwrap()
+    }
+
+    pub fn german() -> Self {
+        GERMAN.parse().unwrap()
+    }
+
+    pub fn spanish() -> Self {
+        SPANISH.parse().unwrap()
+    }
+
+    /// Uppercase Roman numerals from "I" to "XXXIX", so they stand out of lowercase text.
+    pub fn roman() -> Self {
+        let numeral = |n: u32| {
+            let (tens, units) = (n / 10, n % 10);
+            let units = match units {
+                0..=3 => "I".repeat(units as usize),
+                4 => "IV".to_string(),
+                5..=8 => format!("V{}", "I".repeat(units as usize - 5)),
+                _ => "IX".to_string(),
+            };
+
+            format!("{}{}", "X".repeat(tens as usize), units)
+        };
+
+        Vocabulary {
+            words: (1..40).map(|n| (numeral(n), n)).collect(),
+        }
+    }
+
+    /// Loads the vocabulary table at `path`.
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| AocError::Invalid(format!("could not read {}: {}", path.display(), e)))?;
+
+        contents.parse()
+    }
+
+    /// The words of both vocabularies, which must agree on the words they share.
+    pub fn with(mut self, other: Vocabulary) -> Self {
+        self.words.extend(other.words);
+        self
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words
+            .iter()
+            .map(|(word, value)| (word.as_str(), *value))
+    }
+
+    /// A lexer for the digits and the words of the vocabulary.
+    pub fn lexer(&self) -> Result<DigitLexer, AocError> {
+        let digits = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"]
+            .into_iter()
+            .zip(0..);
+
+        DigitLexer::new(digits.chain(self.words()))
+    }
+}
+
+impl FromStr for Vocabulary {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = vec![];
+        let mut values = HashMap::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (word, value) = parse_all(line, parse_word).map_err(|e| e.on_line(i + 1))?;
+            if let Some(previous) = values.insert(word.clone(), value) {
+                return Err(AocError::Invalid(format!(
+                    "line {}: {} is already {}",
+                    i + 1,
+                    word,
+                    previous
+                )));
+            }
+
+            words.push((word, value));
+        }
+
+        Ok(Vocabulary { words })
+    }
+}
+
+fn parse_word(line: &str) -> IResult<&str, (String, u32)> {
+    let (line, (word, value)) = tuple((
+        terminated(take_till1(char::is_whitespace), space1),
+        terminated(u32, space0),
+    ))(line)?;
+
+    Ok((line, (word.to_string(), value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::ErrorKind;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_vocabulary() {
+        let vocabulary = "# comment\n\nuno 1\n  dos 2  \n"
+            .parse::<Vocabulary>()
+            .unwrap();
+
+        assert_eq!(
+            vocabulary.words().collect::<Vec<_>>(),
+            vec![("uno", 1), ("dos", 2)]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "uno 1\ndos x".parse::<Vocabulary>(),
+            Err(AocError::Parse {
+                line: 2,
+                column: 5,
+                kind: ErrorKind::Digit
+            })
+        );
+        assert!(matches!(
+            "uno 1\nuno 2".parse::<Vocabulary>(),
+            Err(AocError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_built_in_vocabularies() {
+        for vocabulary in [
+            Vocabulary::english(),
+            Vocabulary::french(),
+            Vocabulary::german(),
+            Vocabulary::spanish(),
+        ] {
+            assert_eq!(
+                vocabulary.words().filter(|(_, value)| *value > 0).count(),
+                9
+            );
+            assert!(vocabulary.lexer().is_ok());
+        }
+
+        assert_eq!(
+            Vocabulary::load(Path::new("src/day_01/vocabularies/english.txt")),
+            Ok(Vocabulary::english())
+        );
+    }
+
+    #[test]
+    fn test_english_numerals() {
+        let vocabulary = Vocabulary::english_numerals();
+        let words = vocabulary.words().collect::<HashMap<_, _>>();
+
+        assert_eq!(words.len(), 100);
+        assert_eq!(words["zero"], 0);
+        assert_eq!(words["seventeen"], 17);
+        assert_eq!(words["forty"], 40);
+        assert_eq!(words["twenty-one"], 21);
+        assert_eq!(words["ninety-nine"], 99);
+    }
+
+    #[test]
+    fn test_roman() {
+        let vocabulary = Vocabulary::roman();
+        let words = vocabulary.words().collect::<HashMap<_, _>>();
+
+        assert_eq!(words.len(), 39);
+        assert_eq!(words["IV"], 4);
+        assert_eq!(words["VIII"], 8);
+        assert_eq!(words["XIX"], 19);
+        assert_eq!(words["XXXIX"], 39);
+    }
+
+    #[test]
+    fn test_conflicting_vocabularies() {
+        let vocabulary = Vocabulary::english().with("one 2".parse().unwrap());
+
+        assert!(matches!(vocabulary.lexer(), Err(AocError::Invalid(_))));
+    }
+}
@@ -0,0 +1,487 @@
+use std::collections::BTreeSet;
+
+use crate::error::AocError;
+
+use super::{Bag, Game};
+
+/// What is believed about the number of cubes of a colour before seeing any game.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Prior {
+    /// Every amount from none to `max` is as likely.
+    Uniform { max: usize },
+    /// Amounts follow a Poisson distribution of the given mean, cut at `max`.
+    Poisson { mean: f64, max: usize },
+    /// The weight of every amount, starting from none.
+    Weights(Vec<f64>),
+}
+
+impl Prior {
+    /// The logarithm of the weight of every amount the prior allows.
+    fn log_weights(&self) -> Result<Vec<f64>, AocError> {
+        let weights = match self {
+            Prior::Uniform { max } => vec![0.0; max + 1],
+            Prior::Poisson { mean, max } if *mean > 0.0 => {
+                let log_factorials = log_factorials(*max);
+
+                (0..=*max)
+                    .map(|n| n as f64 * mean.ln() - mean - log_factorials[n])
+                    .collect()
+            }
+            Prior::Poisson { mean, .. } => {
+                return Err(AocError::Invalid(format!(
+                    "the mean of a Poisson prior must be positive, not {}",
+                    mean
+                )))
+            }
+            Prior::Weights(weights) => {
+                if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+                    return Err(AocError::Invalid(
+                        "the weights of a prior must be finite and not negative".to_string(),
+                    ));
+                }
+
+                weights.iter().map(|w| w.ln()).collect()
+            }
+        };
+
+        if weights.iter().all(|w| *w == f64::NEG_INFINITY) {
+            return Err(AocError::Invalid("the prior allows no amount".to_string()));
+        }
+
+        Ok(weights)
+    }
+}
+
+/// Whether the cubes of a set go back into the bag before the next set of the same game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Draws {
+    WithReplacement,
+    WithoutReplacement,
+}
+
+/// How the Elf is assumed to draw the sets of a game.
+///
+/// Every cube in the bag is taken into a set on its own with `draw_probability`, so the
+/// amount of a colour shown is binomial and the colours can be inferred one at a time. The
+/// bag is full again at the start of every game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Model {
+    pub prior: Prior,
+    pub draws: Draws,
+    pub draw_probability: f64,
+}
+
+impl Default for Model {
+    /// A uniform prior of up to 100 cubes of a colour, drawn with replacement and as often
+    /// taken as left in the bag, as in the puzzle.
+    fn default() -> Self {
+        Model {
+            prior: Prior::Uniform { max: 100 },
+            draws: Draws::WithReplacement,
+            draw_probability: 0.5,
+        }
+    }
+}
+
+impl Model {
+    /// Infers the contents of the bag the games were played with.
+    pub fn infer(&self, games: &[Game]) -> Result<Inference, AocError> {
+        if !(self.draw_probability > 0.0 && self.draw_probability < 1.0) {
+            return Err(AocError::Invalid(format!(
+                "the draw probability must be between 0 and 1, not {}",
+                self.draw_probability
+            )));
+        }
+
+        let inference = Inference {
+            log_prior: self.prior.log_weights()?,
+            draws: self.draws,
+            draw_probability: self.draw_probability,
+            games: games
+                .iter()
+                .map(|game| game.sets.iter().map(|set| set.to_bag()).collect())
+                .collect(),
+        };
+
+        for color in inference.colors() {
+            if inference
+                .log_weights(color)
+                .iter()
+                .all(|w| *w == f64::NEG_INFINITY)
+            {
+                return Err(AocError::NoSolution(format!(
+                    "no bag the prior allows explains the {} cubes",
+                    color
+                )));
+            }
+        }
+
+        Ok(inference)
+    }
+}
+
+/// What the games tell about the contents of the bag they were played with.
+#[derive(Debug, Clone)]
+pub struct Inference {
+    log_prior: Vec<f64>,
+    draws: Draws,
+    draw_probability: f64,
+    /// The cubes shown in every set of every game.
+    games: Vec<Vec<Bag>>,
+}
+
+impl Inference {
+    /// Every colour shown in the games.
+    pub fn colors(&self) -> BTreeSet<&str> {
+        self.games
+            .iter()
+            .flatten()
+            .flat_map(|set| set.iter().map(|(color, _)| color))
+            .collect()
+    }
+
+    /// The distribution of the amount of cubes of a colour in the bag. A colour never shown
+    /// only becomes less likely to be in the bag with every set.
+    pub fn posterior(&self, color: &str) -> Posterior {
+        let log_weights = self.log_weights(color);
+        let max = log_weights
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let weights = log_weights
+            .iter()
+            .map(|w| (w - max).exp())
+            .collect::<Vec<_>>();
+        let total = weights.iter().sum::<f64>();
+
+        Posterior {
+            probabilities: weights.into_iter().map(|w| w / total).collect(),
+        }
+    }
+
+    /// The bag under which the games are the most likely, whatever the prior believes, but
+    /// within the amounts it allows.
+    pub fn maximum_likelihood_bag(&self) -> Bag {
+        self.colors()
+            .into_iter()
+            .map(|color| {
+                let mut best = (0, f64::NEG_INFINITY);
+                let likelihoods = self.log_prior.iter().zip(self.log_likelihoods(color));
+                for (amount, (prior, likelihood)) in likelihoods.enumerate() {
+                    if *prior > f64::NEG_INFINITY && likelihood > best.1 {
+                        best = (amount, likelihood);
+                    }
+                }
+
+                (color, best.0)
+            })
+            .collect()
+    }
+
+    /// The range of amounts of every colour shown that holds the bag with probability
+    /// `mass`.
+    pub fn credible_intervals(&self, mass: f64) -> Vec<(String, (usize, usize))> {
+        self.colors()
+            .into_iter()
+            .map(|color| {
+                (
+                    color.to_string(),
+                    self.posterior(color).credible_interval(mass),
+                )
+            })
+            .collect()
+    }
+
+    /// The probability that every game the Elf can play with the bag is possible with the
+    /// proposed one, which is that the proposed bag has at least as many cubes of every
+    /// colour.
+    pub fn probability_consistent(&self, bag: &Bag) -> f64 {
+        let mut colors = self.colors();
+        colors.extend(bag.iter().map(|(color, _)| color));
+
+        colors
+            .into_iter()
+            .map(|color| self.posterior(color).probability_at_most(bag.get(color)))
+            .product()
+    }
+
+    /// The logarithm of the weight of every amount of a colour once the games are seen, not
+    /// scaled to add up to one.
+    fn log_weights(&self, color: &str) -> Vec<f64> {
+        self.log_prior
+            .iter()
+            .zip(self.log_likelihoods(color))
+            .map(|(prior, likelihood)| prior + likelihood)
+            .collect()
+    }
+
+    /// The logarithm of the probability of the games for every amount of a colour the prior
+    /// allows.
+    fn log_likelihoods<'a>(&'a self, color: &'a str) -> impl Iterator<Item = f64> + 'a {
+        let log_factorials = log_factorials(self.log_prior.len());
+        let (log_p, log_q) = (
+            self.draw_probability.ln(),
+            (1.0 - self.draw_probability).ln(),
+        );
+
+        (0..self.log_prior.len()).map(move |amount| {
+            let mut likelihood = 0.0;
+
+            for game in self.games.iter() {
+                let mut left = amount;
+
+                for shown in game.iter().map(|set| set.get(color)) {
+                    if shown > left {
+                        return f64::NEG_INFINITY;
+                    }
+
+                    likelihood +=
+                        log_factorials[left] - log_factorials[shown] - log_factorials[left - shown]
+                            + shown as f64 * log_p
+                            + (left - shown) as f64 * log_q;
+
+                    if self.draws == Draws::WithoutReplacement {
+                        left -= shown;
+                    }
+                }
+            }
+
+            likelihood
+        })
+    }
+}
+
+/// The probability of every amount of cubes of a colour, starting from none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Posterior {
+    probabilities: Vec<f64>,
+}
+
+impl Posterior {
+    pub fn probability(&self, amount: usize) -> f64 {
+        self.probabilities.get(amount).copied().unwrap_or(0.0)
+    }
+
+    pub fn probability_at_most(&self, amount: usize) -> f64 {
+        self.probabilities.iter().take(amount + 1).sum()
+    }
+
+    /// The most probable amount, the smallest one if there are several.
+    pub fn mode(&self) -> usize {
+        let mut mode = 0;
+        for (amount, probability) in self.probabilities.iter().enumerate() {
+            if *probability > self.probabilities[mode] {
+                mode = amount;
+            }
+        }
+
+        mode
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.probabilities
+            .iter()
+            .enumerate()
+            .map(|(amount, probability)| amount as f64 * probability)
+            .sum()
+    }
+
+    /// The equal-tailed range of amounts that holds at least `mass` of the probability.
+    pub fn credible_interval(&self, mass: f64) -> (usize, usize) {
+        let tail = (1.0 - mass) / 2.0;
+        let mut cumulative = 0.0;
+        let mut low = None;
+        let mut high = self.probabilities.len() - 1;
+
+        for (amount, probability) in self.probabilities.iter().enumerate() {
+            cumulative += probability;
+
+            if low.is_none() && cumulative > tail {
+                low = Some(amount);
+            }
+
+            if cumulative >= 1.0 - tail {
+                high = amount;
+                break;
+            }
+        }
+
+        (low.unwrap_or(high), high)
+    }
+}
+
+/// The logarithm of the factorials of 0 to `n`.
+fn log_factorials(n: usize) -> Vec<f64> {
+    let mut log_factorials = vec![0.0; n + 1];
+    for i in 1..=n {
+        log_factorials[i] = log_factorials[i - 1] + (i as f64).ln();
+    }
+
+    log_factorials
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::day_02::parse_games;
+
+    fn games(input: &str) -> Vec<Game> {
+        parse_games(input.lines()).unwrap()
+    }
+
+    #[test]
+    fn test_posterior() {
+        let games = games("Game 1: 3 red; 3 red; 3 red; 3 red, 1 blue");
+        let inference = Model::default().infer(&games).unwrap();
+        let red = inference.posterior("red");
+
+        assert!((red.probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(red.probability(2), 0.0);
+        assert_eq!(red.probability(101), 0.0);
+        assert_eq!(red.mode(), 6);
+        assert!(red.mean() > 5.0 && red.mean() < 8.0);
+        assert!(inference.posterior("blue").mode() <= 2);
+        assert_eq!(inference.posterior("purple").mode(), 0);
+    }
+
+    #[test]
+    fn test_draws_without_replacement() {
+        let games = games("Game 1: 3 red; 4 red\nGame 2: 1 red");
+
+        let with = Model::default().infer(&games).unwrap();
+        assert_eq!(with.posterior("red").probability(3), 0.0);
+        assert!(with.posterior("red").probability(4) > 0.0);
+
+        let model = Model {
+            draws: Draws::WithoutReplacement,
+            ..Model::default()
+        };
+        let without = model.infer(&games).unwrap();
+        assert_eq!(without.posterior("red").probability(6), 0.0);
+        assert!(without.posterior("red").probability(7) > 0.0);
+    }
+
+    #[test]
+    fn test_maximum_likelihood_bag() {
+        let games = games("Game 1: 4 red, 1 green; 6 red, 1 green\nGame 2: 5 red, 2 blue");
+        let inference = Model::default().infer(&games).unwrap();
+
+        assert_eq!(
+            inference.maximum_likelihood_bag(),
+            Bag::from_iter([("red", 10), ("green", 1), ("blue", 2)])
+        );
+
+        let model = Model {
+            prior: Prior::Weights(vec![0.0, 0.0, 1.0, 1.0]),
+            ..Model::default()
+        };
+        let inference = model.infer(&self::games("Game 1: 1 red")).unwrap();
+        assert_eq!(
+            inference.maximum_likelihood_bag(),
+            Bag::from_iter([("red", 2)])
+        );
+    }
+
+    #[test]
+    fn test_priors() {
+        let games = games("Game 1: 2 red");
+
+        let model = Model {
+            prior: Prior::Poisson {
+                mean: 12.0,
+                max: 40,
+            },
+            ..Model::default()
+        };
+        let red = model.infer(&games).unwrap().posterior("red");
+        assert!(red.mode() >= 6 && red.mode() <= 10);
+
+        let model = Model {
+            prior: Prior::Weights(vec![0.0, 0.0, 0.0, 1.0, 1.0]),
+            ..Model::default()
+        };
+        let red = model.infer(&games).unwrap().posterior("red");
+        assert_eq!(red.credible_interval(0.99), (3, 4));
+
+        // the prior only allows amounts the games rule out
+        let model = Model {
+            prior: Prior::Weights(vec![1.0, 0.0, 0.0]),
+            ..Model::default()
+        };
+        assert!(matches!(model.infer(&games), Err(AocError::NoSolution(_))));
+
+        let model = Model {
+            prior: Prior::Uniform { max: 1 },
+            ..Model::default()
+        };
+        assert!(matches!(model.infer(&games), Err(AocError::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_invalid_models() {
+        let games = games("Game 1: 2 red");
+
+        for model in [
+            Model {
+                draw_probability: 1.0,
+                ..Model::default()
+            },
+            Model {
+                prior: Prior::Weights(vec![0.0, 0.0]),
+                ..Model::default()
+            },
+            Model {
+                prior: Prior::Weights(vec![1.0, -1.0]),
+                ..Model::default()
+            },
+            Model {
+                prior: Prior::Poisson { mean: 0.0, max: 5 },
+                ..Model::default()
+            },
+        ] {
+            assert!(matches!(model.infer(&games), Err(AocError::Invalid(_))));
+        }
+    }
+
+    #[test]
+    fn test_credible_intervals_and_consistency() {
+        let input = fs::read_to_string("src/day_02/input-01.txt").unwrap();
+        let inference = Model::default().infer(&games(&input)).unwrap();
+
+        for (color, (low, high)) in inference.credible_intervals(0.9) {
+            let posterior = inference.posterior(&color);
+            let mode = posterior.mode();
+
+            assert!(low <= mode && mode <= high, "{}: {} {}", color, low, high);
+            let mass = (low..=high).map(|n| posterior.probability(n)).sum::<f64>();
+            assert!(mass >= 0.9, "{}: {}", color, mass);
+        }
+
+        let mut minimum = Bag::default();
+        for (color, amount) in games(&input)
+            .iter()
+            .flat_map(|game| game.get_minimum_sets_of_cubes().cubes)
+        {
+            let minimum_amount = minimum.cubes.entry(color).or_default();
+            *minimum_amount = (*minimum_amount).max(amount);
+        }
+        let below = minimum
+            .iter()
+            .map(|(color, amount)| (color, amount - 1))
+            .collect::<Bag>();
+        let huge = minimum
+            .iter()
+            .map(|(color, _)| (color, 100))
+            .collect::<Bag>();
+
+        assert_eq!(inference.probability_consistent(&below), 0.0);
+        assert!(inference.probability_consistent(&minimum) > 0.0);
+        assert!((inference.probability_consistent(&huge) - 1.0).abs() < 1e-9);
+        assert!(
+            inference.probability_consistent(&Bag::puzzle())
+                < inference.probability_consistent(&huge)
+        );
+    }
+}
@@ -1,182 +1,325 @@
-use nom::{bytes::complete::tag, bytes::complete::take_until};
-use std::{cmp, str::FromStr};
+mod inference;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, space1, u64},
+    combinator::cut,
+    multi::separated_list1,
+    sequence::{delimited, terminated},
+    IResult,
+};
+use std::{cmp, collections::BTreeMap, fmt::Display, io::BufRead, str::FromStr};
+
+pub use inference::{Draws, Inference, Model, Posterior, Prior};
+
+use crate::error::{parse_all, AocError};
+use crate::input::for_each_line;
+use crate::solution::{Answer, Solution, Streaming};
+
+/// Sums the indices of the games read from `reader` that are possible with the bag of the
+/// puzzle, one game at a time.
+pub fn part_01<R: BufRead>(reader: R) -> Result<usize, AocError> {
+    sum_possible_games(reader, &Bag::puzzle())
+}
 
-pub fn part_01(lines: &Vec<&str>) -> usize {
-    lines
-        .iter()
-        .map(|l| l.parse::<Game>().unwrap())
-        .filter(|g| g.is_possible())
-        .map(|g| g.get_index())
-        .sum::<usize>()
+/// Sums the indices of the games read from `reader` that are possible with `bag`, one game
+/// at a time.
+pub fn sum_possible_games<R: BufRead>(reader: R, bag: &Bag) -> Result<usize, AocError> {
+    let mut sum = 0;
+    for_each_game(reader, |game| {
+        if game.is_possible(bag) {
+            sum += game.get_index();
+        }
+    })?;
+
+    Ok(sum)
 }
 
-pub fn part_02(lines: &Vec<&str>) -> usize {
-    lines
-        .iter()
-        .map(|l| l.parse::<Game>().unwrap())
-        .map(|g| g.get_minimum_sets_of_cubes())
-        .map(|set| set.get_power())
-        .sum::<usize>()
+/// Sums the powers of the minimum sets of cubes of the games read from `reader`, one game
+/// at a time.
+pub fn part_02<R: BufRead>(reader: R) -> Result<usize, AocError> {
+    let mut sum = 0;
+    for_each_game(reader, |game| {
+        sum += game.get_puzzle_power();
+    })?;
+
+    Ok(sum)
 }
 
-#[derive(Debug)]
-enum Cube {
-    Red(usize),
-    Green(usize),
-    Blue(usize),
+pub struct Day02 {
+    games: Vec<Game>,
 }
 
-impl Cube {
-    fn is_possible(&self) -> bool {
-        match self {
-            Cube::Red(amount) => amount <= &12,
-            Cube::Green(amount) => amount <= &13,
-            Cube::Blue(amount) => amount <= &14,
-        }
+impl Solution for Day02 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day02 {
+            games: parse_games(input.lines())?,
+        })
     }
 
-    fn get_amount(&self) -> usize {
-        match self {
-            Cube::Red(amount) => *amount,
-            Cube::Green(amount) => *amount,
-            Cube::Blue(amount) => *amount,
-        }
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.games
+                .iter()
+                .filter(|g| g.is_possible(&Bag::puzzle()))
+                .map(|g| g.get_index())
+                .sum::<usize>(),
+        ))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.games
+                .iter()
+                .map(|g| g.get_puzzle_power())
+                .sum::<usize>(),
+        ))
+    }
+}
+
+impl Streaming for Day02 {
+    fn stream_part_one(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_01(reader)?))
     }
 
-    fn is_same_color(&self, other: &Cube) -> bool {
-        matches!(
-            (self, other),
-            (Cube::Red(_), Cube::Red(_))
-                | (Cube::Green(_), Cube::Green(_))
-                | (Cube::Blue(_), Cube::Blue(_))
-        )
+    fn stream_part_two(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_02(reader)?))
+    }
+}
+
+fn for_each_game<R: BufRead>(reader: R, mut f: impl FnMut(Game)) -> Result<(), AocError> {
+    for_each_line(reader, |i, line| {
+        f(line.parse::<Game>().map_err(|e| e.on_line(i))?);
+
+        Ok(())
+    })
+}
+
+fn parse_games<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Vec<Game>, AocError> {
+    lines
+        .enumerate()
+        .map(|(i, l)| l.parse::<Game>().map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+fn parse_game(input: &str) -> IResult<&str, Game> {
+    let (input, index) = delimited(tag("Game "), u64, tag(": "))(input)?;
+    let (input, sets) = separated_list1(tag("; "), parse_set)(input)?;
+
+    Ok((
+        input,
+        Game {
+            index: index as usize,
+            sets,
+        },
+    ))
+}
+
+fn parse_set(input: &str) -> IResult<&str, Set> {
+    let (input, cubes) = separated_list1(tag(", "), parse_cube)(input)?;
+
+    Ok((input, Set { cubes }))
+}
+
+fn parse_cube(input: &str) -> IResult<&str, Cube> {
+    let (input, amount) = terminated(u64, space1)(input)?;
+    // once there is an amount the colour must follow, so a missing colour is reported where
+    // it is instead of where the list of cubes stopped
+    let (input, color) = cut(alpha1)(input)?;
+
+    Ok((
+        input,
+        Cube {
+            color: color.to_string(),
+            amount: amount as usize,
+        },
+    ))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Cube {
+    color: String,
+    amount: usize,
+}
+
+impl Display for Cube {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.color)
     }
 }
 
 impl FromStr for Cube {
-    type Err = ();
+    type Err = AocError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (color, amount) = take_until::<_, _, ()>(" ")(input.trim()).map_err(|_| ())?;
-        let amount = amount.trim().parse::<usize>().map_err(|_| ())?;
-        let cube = match color.trim() {
-            "red" => Cube::Red(amount),
-            "green" => Cube::Green(amount),
-            "blue" => Cube::Blue(amount),
-            _ => return Err(()),
-        };
-
-        Ok(cube)
+        parse_all(input.trim(), parse_cube)
     }
 }
 
-#[derive(Debug)]
-struct Set {
-    cubes: Vec<Cube>,
+/// How many cubes of every colour there are, a colour that is not in the bag having none.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Bag {
+    cubes: BTreeMap<String, usize>,
 }
 
-impl Set {
-    fn is_possible(&self) -> bool {
-        self.cubes.iter().all(|c| c.is_possible())
+impl Bag {
+    /// 12 red, 13 green and 14 blue cubes, as in the puzzle.
+    pub fn puzzle() -> Self {
+        Bag::from_iter([("red", 12), ("green", 13), ("blue", 14)])
     }
 
-    fn get_red_amount(&self) -> Option<usize> {
-        self.get_color_amount(&Cube::Red(0))
+    pub fn get(&self, color: &str) -> usize {
+        self.cubes.get(color).copied().unwrap_or(0)
     }
 
-    fn get_green_amount(&self) -> Option<usize> {
-        self.get_color_amount(&Cube::Green(0))
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.cubes
+            .iter()
+            .map(|(color, amount)| (color.as_str(), *amount))
     }
 
-    fn get_blue_amount(&self) -> Option<usize> {
-        self.get_color_amount(&Cube::Blue(0))
+    /// The product of the amounts of every colour in the bag.
+    pub fn get_power(&self) -> usize {
+        self.cubes.values().product()
     }
+}
+
+impl<S: Into<String>> FromIterator<(S, usize)> for Bag {
+    fn from_iter<I: IntoIterator<Item = (S, usize)>>(iter: I) -> Self {
+        let mut bag = Bag::default();
+        for (color, amount) in iter {
+            *bag.cubes.entry(color.into()).or_default() += amount;
+        }
+
+        bag
+    }
+}
 
-    fn get_color_amount(&self, cube: &Cube) -> Option<usize> {
+/// Writes a bag like a set of cubes: `14 blue, 13 green, 12 red`.
+impl Display for Bag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (color, amount)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", amount, color)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a bag written like a set of cubes: `12 red, 13 green, 14 blue`.
+impl FromStr for Bag {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(input.trim().parse::<Set>()?.to_bag())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Set {
+    cubes: Vec<Cube>,
+}
+
+impl Set {
+    fn is_possible(&self, bag: &Bag) -> bool {
+        self.to_bag()
+            .iter()
+            .all(|(color, amount)| amount <= bag.get(color))
+    }
+
+    /// The cubes shown, adding up the amounts of a colour that is shown more than once.
+    fn to_bag(&self) -> Bag {
         self.cubes
             .iter()
-            .find(|c| c.is_same_color(cube))
-            .map(|c| c.get_amount())
+            .map(|cube| (cube.color.as_str(), cube.amount))
+            .collect()
     }
+}
+
+impl Display for Set {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, cube) in self.cubes.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", cube)?;
+        }
 
-    fn get_power(&self) -> usize {
-        self.get_red_amount().unwrap_or(1)
-            * self.get_green_amount().unwrap_or(1)
-            * self.get_blue_amount().unwrap_or(1)
+        Ok(())
     }
 }
 
 impl FromStr for Set {
-    type Err = ();
+    type Err = AocError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let cubes = input.split(", ").collect::<Vec<_>>();
-
-        Ok(Set {
-            cubes: cubes
-                .iter()
-                .map(|c| c.parse::<Cube>())
-                .collect::<Result<Vec<_>, _>>()?,
-        })
+        parse_all(input, parse_set)
     }
 }
 
-#[derive(Debug)]
-struct Game {
+#[derive(Debug, PartialEq, Eq)]
+pub struct Game {
     index: usize,
     sets: Vec<Set>,
 }
 
 impl Game {
-    fn is_possible(&self) -> bool {
-        self.sets.iter().all(|s| s.is_possible())
+    /// Whether every set of the game could have been drawn from the bag.
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.sets.iter().all(|s| s.is_possible(bag))
     }
 
-    fn get_index(&self) -> usize {
+    pub fn get_index(&self) -> usize {
         self.index
     }
 
-    fn get_minimum_sets_of_cubes(&self) -> Set {
-        let mut red = 0;
-        let mut green = 0;
-        let mut blue = 0;
+    /// The smallest bag the game is possible with, which has every colour the game shows.
+    pub fn get_minimum_sets_of_cubes(&self) -> Bag {
+        let mut minimum = Bag::default();
 
-        for set in &self.sets {
-            if let Some(red_amount) = set.get_red_amount() {
-                red = cmp::max(red, red_amount);
-            }
+        for (color, amount) in self.sets.iter().flat_map(|set| set.to_bag().cubes) {
+            let minimum_amount = minimum.cubes.entry(color).or_default();
+            *minimum_amount = cmp::max(*minimum_amount, amount);
+        }
 
-            if let Some(green_amount) = set.get_green_amount() {
-                green = cmp::max(green, green_amount);
-            }
+        minimum
+    }
+
+    /// The power of the minimum set of cubes over the colours of the puzzle, which is zero
+    /// when the game never shows one of them.
+    pub fn get_puzzle_power(&self) -> usize {
+        let minimum = self.get_minimum_sets_of_cubes();
+
+        Bag::puzzle()
+            .iter()
+            .map(|(color, _)| minimum.get(color))
+            .product()
+    }
+}
 
-            if let Some(blue_amount) = set.get_blue_amount() {
-                blue = cmp::max(blue, blue_amount);
+/// Writes the game as a line of the puzzle input.
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}: ", self.index)?;
+        for (i, set) in self.sets.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
             }
+            write!(f, "{}", set)?;
         }
 
-        Set {
-            cubes: vec![Cube::Red(red), Cube::Green(green), Cube::Blue(blue)],
-        }
+        Ok(())
     }
 }
 
 impl FromStr for Game {
-    type Err = ();
+    type Err = AocError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (input, _) = tag::<_, _, ()>("Game ")(input).map_err(|_| ())?;
-        let (input, index) = take_until::<_, _, ()>(": ")(input).map_err(|_| ())?;
-        let (input, _) = tag::<_, _, ()>(": ")(input).map_err(|_| ())?;
-        let sets = input.split("; ").collect::<Vec<_>>();
-
-        Ok(Game {
-            index: index.parse::<usize>().map_err(|_| ())?,
-            sets: sets
-                .iter()
-                .map(|s| s.parse::<Set>())
-                .collect::<Result<Vec<_>, _>>()?,
-        })
+        parse_all(input, parse_game)
     }
 }
 
@@ -184,8 +327,16 @@ impl FromStr for Game {
 mod tests {
 
     use super::*;
+    use crate::generate::generate;
+    use proptest::prelude::*;
     use std::fs;
 
+    fn assert_display_round_trip(input: &str) {
+        for game in parse_games(input.lines()).unwrap() {
+            assert_eq!(game.to_string().parse::<Game>().unwrap(), game);
+        }
+    }
+
     #[test]
     fn test_part_01_with_test_input() {
         let input = fs::read_to_string("src/day_02/test-input-01.txt").unwrap();
@@ -197,7 +348,7 @@ mod tests {
         assert_eq!(
             games
                 .iter()
-                .filter(|g| g.is_possible())
+                .filter(|g| g.is_possible(&Bag::puzzle()))
                 .map(|g| g.get_index())
                 .sum::<usize>(),
             8
@@ -222,37 +373,91 @@ mod tests {
     }
 
     #[test]
-    fn test_part_01_with_complete_input() {
-        let input = fs::read_to_string("src/day_02/input-01.txt").unwrap();
-        let games = input
-            .lines()
-            .map(|l| l.parse::<Game>().unwrap())
-            .collect::<Vec<_>>();
+    fn test_part_02_with_a_colour_never_shown() {
+        let game = "Game 1: 3 red, 4 green; 1 red".parse::<Game>().unwrap();
+
+        assert_eq!(game.get_minimum_sets_of_cubes().get_power(), 12);
+        assert_eq!(game.get_puzzle_power(), 0);
+        assert_eq!(part_02("Game 1: 3 red, 4 green; 1 red".as_bytes()), Ok(0));
+    }
+
+    #[test]
+    fn test_stream_with_test_input() {
+        let input = fs::read_to_string("src/day_02/test-input-01.txt").unwrap();
+
+        assert_eq!(part_01(input.as_bytes()), Ok(8));
+        assert_eq!(part_02(input.as_bytes()), Ok(2286));
+    }
+
+    #[test]
+    fn test_any_colour() {
+        let input = "Game 1: 3 blue, 4 red; 2 purple\nGame 2: 1 blue, 5 purple, 1 teal";
+        let games = parse_games(input.lines()).unwrap();
 
         assert_eq!(
-            games
-                .iter()
-                .filter(|g| g.is_possible())
-                .map(|g| g.get_index())
-                .sum::<usize>(),
-            2239
+            games[1].get_minimum_sets_of_cubes(),
+            Bag::from_iter([("blue", 1), ("purple", 5), ("teal", 1)])
         );
+        assert_eq!(sum_possible_games(input.as_bytes(), &Bag::puzzle()), Ok(0));
+
+        let bag = "3 blue, 4 red, 2 purple".parse::<Bag>().unwrap();
+        assert!(games[0].is_possible(&bag));
+        assert!(!games[1].is_possible(&bag));
+
+        let bag = Bag::from_iter([("blue", 3), ("red", 4), ("purple", 5), ("teal", 1)]);
+        assert_eq!(sum_possible_games(input.as_bytes(), &bag), Ok(3));
     }
 
     #[test]
-    fn test_part_02_with_complete_input() {
-        let input = fs::read_to_string("src/day_02/input-01.txt").unwrap();
-        let games = input
-            .lines()
-            .map(|l| l.parse::<Game>().unwrap())
-            .collect::<Vec<_>>();
+    fn test_minimum_sets_of_cubes() {
+        let game = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            .parse::<Game>()
+            .unwrap();
+        let minimum = game.get_minimum_sets_of_cubes();
 
-        let sum_of_powers = games
-            .iter()
-            .map(|g| g.get_minimum_sets_of_cubes())
-            .map(|set| set.get_power())
-            .sum::<usize>();
+        assert_eq!(
+            minimum.iter().collect::<Vec<_>>(),
+            vec![("blue", 6), ("green", 2), ("red", 4)]
+        );
+        assert_eq!(minimum.get("purple"), 0);
+        assert_eq!(minimum.get_power(), 48);
+        assert!(game.is_possible(&minimum));
+    }
+
+    #[test]
+    fn test_malformed_game() {
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2 3";
+
+        assert_eq!(
+            part_01(input.as_bytes()),
+            Err(AocError::Parse {
+                line: 2,
+                column: 19,
+                kind: nom::error::ErrorKind::Alpha
+            })
+        );
+    }
 
-        assert_eq!(sum_of_powers, 83435);
+    #[test]
+    fn test_display_round_trip() {
+        for path in ["src/day_02/input-01.txt", "src/day_02/test-input-01.txt"] {
+            let input = fs::read_to_string(path).unwrap();
+            assert_display_round_trip(&input);
+
+            for line in input.lines() {
+                assert_eq!(line.parse::<Game>().unwrap().to_string(), line);
+            }
+        }
+
+        let bag = Bag::puzzle();
+        assert_eq!(bag.to_string(), "14 blue, 13 green, 12 red");
+        assert_eq!(bag.to_string().parse::<Bag>().unwrap(), bag);
+    }
+
+    proptest! {
+        #[test]
+        fn test_display_round_trip_of_generated_games(seed in any::<u64>(), size in 0..20usize) {
+            assert_display_round_trip(&generate(2, seed, size).unwrap());
+        }
     }
 }
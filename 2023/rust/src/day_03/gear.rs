@@ -0,0 +1,117 @@
+use std::fmt::Display;
+
+use super::schematic::Number;
+
+/// How many numbers a symbol must be next to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeighbourCount {
+    Exactly(usize),
+    AtLeast(usize),
+    AtMost(usize),
+}
+
+impl NeighbourCount {
+    fn allows(&self, count: usize) -> bool {
+        match self {
+            NeighbourCount::Exactly(n) => count == *n,
+            NeighbourCount::AtLeast(n) => count >= *n,
+            NeighbourCount::AtMost(n) => count <= *n,
+        }
+    }
+}
+
+/// How the numbers next to a gear are combined into its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduction {
+    Product,
+    Sum,
+    /// The largest number, or 0 when there is none.
+    Max,
+}
+
+impl Reduction {
+    pub fn reduce<I: IntoIterator<Item = isize>>(&self, values: I) -> isize {
+        let values = values.into_iter();
+
+        match self {
+            Reduction::Product => values.product(),
+            Reduction::Sum => values.sum(),
+            Reduction::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+impl Display for Reduction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reduction::Product => write!(f, "product"),
+            Reduction::Sum => write!(f, "sum"),
+            Reduction::Max => write!(f, "max"),
+        }
+    }
+}
+
+/// Which symbols are gears and what they are worth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    pub neighbours: NeighbourCount,
+    pub reduction: Reduction,
+}
+
+impl GearRule {
+    /// A `*` next to exactly two numbers, worth their product, as in the puzzle.
+    pub fn puzzle() -> Self {
+        GearRule {
+            symbols: vec!['*'],
+            neighbours: NeighbourCount::Exactly(2),
+            reduction: Reduction::Product,
+        }
+    }
+
+    pub fn is_gear(&self, symbol: char, numbers: &[&Number]) -> bool {
+        self.symbols.contains(&symbol) && self.neighbours.allows(numbers.len())
+    }
+}
+
+/// A symbol that follows a gear rule, with the numbers next to it in the order they are
+/// written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gear<'a> {
+    pub position: (usize, usize),
+    pub symbol: char,
+    pub numbers: Vec<&'a Number>,
+}
+
+impl Gear<'_> {
+    pub fn reduce(&self, reduction: Reduction) -> isize {
+        reduction.reduce(self.numbers.iter().map(|n| n.value))
+    }
+
+    pub fn ratio(&self) -> isize {
+        self.reduce(Reduction::Product)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbour_count() {
+        assert!(NeighbourCount::Exactly(2).allows(2));
+        assert!(!NeighbourCount::Exactly(2).allows(3));
+        assert!(NeighbourCount::AtLeast(2).allows(3));
+        assert!(!NeighbourCount::AtLeast(2).allows(1));
+        assert!(NeighbourCount::AtMost(1).allows(0));
+        assert!(!NeighbourCount::AtMost(1).allows(2));
+    }
+
+    #[test]
+    fn test_reduction() {
+        assert_eq!(Reduction::Product.reduce([2, 3, 4]), 24);
+        assert_eq!(Reduction::Sum.reduce([2, 3, 4]), 9);
+        assert_eq!(Reduction::Max.reduce([2, 7, 4]), 7);
+        assert_eq!(Reduction::Max.reduce([]), 0);
+    }
+}
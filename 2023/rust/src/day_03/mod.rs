@@ -1,166 +1,45 @@
-use std::num::ParseIntError;
+mod gear;
+mod render;
+mod schematic;
 
-pub fn part_01(lines: &Vec<&str>) -> Result<isize, ParseIntError> {
-    let blocks = get_blocks(lines);
+use crate::error::AocError;
+use crate::solution::{Answer, Solution};
 
-    let number_blocks_adjacent_to_symbols = blocks
-        .iter()
-        .filter(|b| b.r#type == BlockType::Number)
-        .filter(|b| b.touches_symbol(&blocks))
-        .collect::<Vec<&Block>>();
+pub use gear::{Gear, GearRule, NeighbourCount, Reduction};
+pub use render::{render, Format};
+pub use schematic::{Number, Schematic, SymbolSummary};
 
-    Ok(number_blocks_adjacent_to_symbols
-        .iter()
-        .map(|b| b.value.parse::<isize>().unwrap())
-        .sum::<isize>())
-}
-
-pub fn part_02(lines: &Vec<&str>) -> Result<isize, ParseIntError> {
-    let blocks = get_blocks(lines);
-    Ok(blocks
-        .iter()
-        .filter(|b| b.is_gear(&blocks))
-        .map(|b| b.get_adjacent_numbers(&blocks))
-        .map(|b| {
-            b.iter()
-                .map(|b| b.value.parse::<isize>().unwrap())
-                .product::<isize>()
-        })
-        .sum::<isize>())
-}
+pub fn part_01(lines: &[&str]) -> Result<isize, AocError> {
+    let schematic = Schematic::new(lines)?;
 
-fn get_blocks(lines: &Vec<&str>) -> Vec<Block> {
-    let mut blocks = Vec::new();
-
-    for (line_index, line_contents) in lines.iter().enumerate() {
-        blocks.append(&mut get_line_blocks(line_contents, line_index));
-    }
-
-    blocks
+    Ok(schematic.sum_part_numbers())
 }
 
-fn get_line_blocks(line: &str, line_index: usize) -> Vec<Block> {
-    let mut blocks = Vec::new();
-
-    let mut i = 0;
-    while i < line.len() {
-        let character = line.chars().nth(i).unwrap();
-
-        let block = if character.is_digit(10) {
-            Block {
-                line: line_index as isize,
-                offset: i as isize,
-                value: get_number(line, i).unwrap(),
-                r#type: BlockType::Number,
-            }
-        } else if character == '.' {
-            Block {
-                line: line_index as isize,
-                offset: i as isize,
-                value: get_dots(line, i).unwrap(),
-                r#type: BlockType::Dot,
-            }
-        } else {
-            Block {
-                line: line_index as isize,
-                offset: i as isize,
-                value: get_symbol(line, i).unwrap(),
-                r#type: BlockType::Symbol,
-            }
-        };
-
-        i = i + block.len() as usize;
-        blocks.push(block);
-    }
-
-    blocks
-}
-
-fn get_number(line: &str, offset: usize) -> Option<String> {
-    get_sequence(line, offset, |c| c.is_digit(10))
-}
-
-fn get_dots(line: &str, offset: usize) -> Option<String> {
-    get_sequence(line, offset, |c| c == '.')
-}
-
-fn get_symbol(line: &str, offset: usize) -> Option<String> {
-    get_sequence(line, offset, |c| !c.is_digit(10) && c != '.')
-}
-
-fn get_sequence<F>(line: &str, offset: usize, predicate: F) -> Option<String>
-where
-    F: Fn(char) -> bool,
-{
-    let mut sequence = String::from("");
-
-    for character in line[offset..].chars() {
-        if predicate(character) {
-            sequence.push(character);
-        } else {
-            break;
-        }
-    }
-
-    if sequence.len() == 0 {
-        return None;
-    }
-
-    Some(sequence)
-}
+pub fn part_02(lines: &[&str]) -> Result<isize, AocError> {
+    let schematic = Schematic::new(lines)?;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum BlockType {
-    Number,
-    Dot,
-    Symbol,
+    Ok(schematic.sum_gear_ratios())
 }
 
-#[derive(Debug, Clone)]
-struct Block {
-    value: String,
-    line: isize,
-    offset: isize,
-    r#type: BlockType,
+pub struct Day03 {
+    schematic: Schematic,
 }
 
-impl Block {
-    fn len(&self) -> isize {
-        self.value.len() as isize
-    }
-
-    fn is_gear(&self, blocks: &Vec<Block>) -> bool {
-        if self.value != "*" {
-            return false;
-        }
-
-        self.has_exactly_two_adjacent_numbers(blocks)
-    }
-
-    fn get_adjacent_numbers(&self, blocks: &Vec<Block>) -> Vec<Block> {
-        blocks
-            .iter()
-            .filter(|b| b.r#type == BlockType::Number)
-            .filter(|b| b.line == self.line - 1 || b.line == self.line || b.line == self.line + 1)
-            .filter(|b| b.offset + b.len() >= self.offset && b.offset <= self.offset + self.len())
-            .map(|b| b.clone())
-            .collect::<Vec<Block>>()
-    }
+impl Solution for Day03 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let lines = input.lines().collect::<Vec<_>>();
 
-    fn count_adjacent_numbers(&self, blocks: &Vec<Block>) -> usize {
-        self.get_adjacent_numbers(blocks).len()
+        Ok(Day03 {
+            schematic: Schematic::new(&lines)?,
+        })
     }
 
-    fn has_exactly_two_adjacent_numbers(&self, blocks: &Vec<Block>) -> bool {
-        self.count_adjacent_numbers(blocks) == 2
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.schematic.sum_part_numbers()))
     }
 
-    fn touches_symbol(&self, blocks: &Vec<Block>) -> bool {
-        blocks
-            .iter()
-            .filter(|b| b.r#type == BlockType::Symbol)
-            .filter(|b| b.line == self.line - 1 || b.line == self.line || b.line == self.line + 1)
-            .any(|b| b.offset + b.len() >= self.offset && b.offset <= self.offset + self.len())
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.schematic.sum_gear_ratios()))
     }
 }
 
@@ -169,6 +48,7 @@ mod tests {
 
     use super::*;
     use std::fs;
+    use std::ops::Range;
 
     #[test]
     fn test_part_01_test_input() {
@@ -179,16 +59,6 @@ mod tests {
         assert_eq!(sum, 4361);
     }
 
-    #[test]
-    fn test_part_01_complete_input() {
-        let lines = fs::read_to_string("src/day_03/input-01.txt").unwrap();
-        let lines = lines.split("\n").collect::<Vec<&str>>();
-
-        let sum = part_01(&lines).unwrap();
-
-        assert_eq!(sum, 556057);
-    }
-
     #[test]
     fn test_part_02_test_input() {
         let lines = fs::read_to_string("src/day_03/test-input-01.txt").unwrap();
@@ -198,13 +68,79 @@ mod tests {
         assert_eq!(sum, 467835);
     }
 
-    #[test]
-    fn test_part_02_complete_input() {
-        let lines = fs::read_to_string("src/day_03/input-01.txt").unwrap();
-        let lines = lines.split("\n").collect::<Vec<&str>>();
+    /// The sums of part numbers and gear ratios found by looking at the characters around
+    /// every number and symbol, to check the indexed schematic against.
+    fn brute_force_sums(input: &str) -> (isize, isize) {
+        let lines = input
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // the value of every number, its line and the columns it spans
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        for (y, line) in lines.iter().enumerate() {
+            let mut x = 0;
+            while x < line.len() {
+                let start = x;
+                while x < line.len() && line[x].is_ascii_digit() {
+                    x += 1;
+                }
+
+                if start < x {
+                    let value = line[start..x].iter().collect::<String>();
+                    numbers.push((value.parse::<isize>().unwrap(), y, start..x));
+                } else {
+                    if line[x] != '.' {
+                        symbols.push((line[x], x, y));
+                    }
+                    x += 1;
+                }
+            }
+        }
 
-        let sum = part_02(&lines).unwrap();
+        fn is_around(
+            (_, line, columns): &(isize, usize, Range<usize>),
+            x: usize,
+            y: usize,
+        ) -> bool {
+            y + 1 >= *line && y <= line + 1 && x + 1 >= columns.start && x <= columns.end
+        }
 
-        assert_eq!(sum, 82824352);
+        let parts = numbers
+            .iter()
+            .filter(|number| symbols.iter().any(|&(_, x, y)| is_around(number, x, y)))
+            .map(|(value, _, _)| value)
+            .sum();
+        let gears = symbols
+            .iter()
+            .filter(|(symbol, _, _)| *symbol == '*')
+            .map(|&(_, x, y)| {
+                numbers
+                    .iter()
+                    .filter(|number| is_around(number, x, y))
+                    .map(|(value, _, _)| *value)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|values| values.len() == 2)
+            .map(|values| values[0] * values[1])
+            .sum();
+
+        (parts, gears)
+    }
+
+    #[test]
+    fn test_generated_schematics_match_a_brute_force_count() {
+        for seed in 0..20 {
+            let input = crate::generate::generate(3, seed, 60).unwrap();
+            let schematic = Schematic::new(&input.lines().collect::<Vec<_>>()).unwrap();
+
+            assert_eq!(
+                (schematic.sum_part_numbers(), schematic.sum_gear_ratios()),
+                brute_force_sums(&input),
+                "seed {}",
+                seed
+            );
+        }
     }
 }
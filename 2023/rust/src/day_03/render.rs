@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::gear::{Gear, GearRule, Reduction};
+use super::schematic::Schematic;
+
+const HTML_STYLE: &str = "<style>\
+.schematic .part { color: green; } \
+.schematic .orphan { color: red; } \
+.schematic .symbol { color: teal; } \
+.schematic .gear { color: darkorange; font-weight: bold; } \
+.schematic .ratio { color: gray; }\
+</style>";
+
+/// How a rendered schematic is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Colours for a terminal.
+    Ansi,
+    /// A `pre` element with a class for every kind of cell.
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Dot,
+    /// A digit of a number next to a symbol.
+    Part,
+    /// A digit of a number next to no symbol.
+    Orphan,
+    Symbol,
+    Gear,
+}
+
+impl Kind {
+    fn ansi(&self) -> Option<&'static str> {
+        match self {
+            Kind::Dot => None,
+            Kind::Part => Some("\x1b[32m"),
+            Kind::Orphan => Some("\x1b[31m"),
+            Kind::Symbol => Some("\x1b[36m"),
+            Kind::Gear => Some("\x1b[1;33m"),
+        }
+    }
+
+    fn class(&self) -> Option<&'static str> {
+        match self {
+            Kind::Dot => None,
+            Kind::Part => Some("part"),
+            Kind::Orphan => Some("orphan"),
+            Kind::Symbol => Some("symbol"),
+            Kind::Gear => Some("gear"),
+        }
+    }
+}
+
+/// Writes `schematic` back out with part numbers, orphan numbers, symbols and the gears by
+/// `rule` each highlighted their own way, and the value of every gear after its line.
+pub fn render(schematic: &Schematic, rule: GearRule, format: Format) -> String {
+    let (width, height) = schematic.size();
+    let reduction = rule.reduction;
+    let mut lines = vec![vec![('.', Kind::Dot); width]; height];
+
+    for number in schematic.numbers() {
+        let kind = match schematic.get_symbols_around(number).is_empty() {
+            true => Kind::Orphan,
+            false => Kind::Part,
+        };
+        let digits = format!("{:0width$}", number.value, width = number.len);
+
+        for (x, digit) in (number.offset..).zip(digits.chars()) {
+            lines[number.line][x] = (digit, kind);
+        }
+    }
+
+    for ((x, y), symbol) in schematic.symbols() {
+        lines[y][x] = (symbol, Kind::Symbol);
+    }
+
+    let mut gears = HashMap::<usize, Vec<Gear>>::new();
+    for gear in schematic.gears_with(rule) {
+        let (x, y) = gear.position;
+        lines[y][x].1 = Kind::Gear;
+        gears.entry(y).or_default().push(gear);
+    }
+
+    let mut rendered = String::new();
+    if format == Format::Html {
+        rendered.push_str(HTML_STYLE);
+        rendered.push_str("<pre class=\"schematic\">");
+    }
+
+    for (y, line) in lines.iter().enumerate() {
+        let mut cells = line.iter().peekable();
+        while let Some(&(c, kind)) = cells.next() {
+            let mut run = c.to_string();
+            while let Some(&(c, _)) = cells.next_if(|(_, k)| *k == kind) {
+                run.push(c);
+            }
+
+            push_run(&mut rendered, &run, kind, format);
+        }
+
+        for gear in gears.get(&y).into_iter().flatten() {
+            rendered.push_str("  ");
+            push_annotation(&mut rendered, &annotate(gear, reduction), format);
+        }
+        rendered.push('\n');
+    }
+
+    if format == Format::Html {
+        rendered.push_str("</pre>\n");
+    }
+
+    rendered
+}
+
+/// What a gear is worth and why, e.g. `* at (3, 1) = 16345 (product of 467, 35)`.
+fn annotate(gear: &Gear, reduction: Reduction) -> String {
+    let numbers = gear
+        .numbers
+        .iter()
+        .map(|n| n.value.to_string())
+        .collect::<Vec<_>>();
+
+    format!(
+        "{} at ({}, {}) = {} ({} of {})",
+        gear.symbol,
+        gear.position.0,
+        gear.position.1,
+        gear.reduce(reduction),
+        reduction,
+        numbers.join(", ")
+    )
+}
+
+fn push_run(rendered: &mut String, run: &str, kind: Kind, format: Format) {
+    match format {
+        Format::Ansi => match kind.ansi() {
+            Some(colour) => write!(rendered, "{}{}\x1b[0m", colour, run).unwrap(),
+            None => rendered.push_str(run),
+        },
+        Format::Html => match kind.class() {
+            Some(class) => write!(
+                rendered,
+                "<span class=\"{}\">{}</span>",
+                class,
+                escape_html(run)
+            )
+            .unwrap(),
+            None => rendered.push_str(&escape_html(run)),
+        },
+    }
+}
+
+fn push_annotation(rendered: &mut String, annotation: &str, format: Format) {
+    match format {
+        Format::Ansi => write!(rendered, "\x1b[2m{}\x1b[0m", annotation).unwrap(),
+        Format::Html => write!(
+            rendered,
+            "<span class=\"ratio\">{}</span>",
+            escape_html(annotation)
+        )
+        .unwrap(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINES: [&str; 5] = [
+        "467..114..",
+        "...*......",
+        "..35..633.",
+        "......#...",
+        "617*......",
+    ];
+
+    fn strip_ansi(rendered: &str) -> String {
+        let mut stripped = String::new();
+        let mut chars = rendered.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => {
+                    chars.by_ref().find(|&c| c == 'm');
+                }
+                c => stripped.push(c),
+            }
+        }
+
+        stripped
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let schematic = Schematic::new(&LINES).unwrap();
+        let rendered = render(&schematic, GearRule::puzzle(), Format::Ansi);
+
+        assert!(rendered.starts_with("\x1b[32m467\x1b[0m..\x1b[31m114\x1b[0m..\n"));
+        assert!(rendered.contains("\x1b[1;33m*\x1b[0m"));
+        assert!(rendered.contains("\x1b[36m#\x1b[0m"));
+        assert_eq!(
+            strip_ansi(&rendered).lines().collect::<Vec<_>>(),
+            vec![
+                "467..114..",
+                "...*......  * at (3, 1) = 16345 (product of 467, 35)",
+                "..35..633.",
+                "......#...",
+                "617*......",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_html() {
+        let schematic = Schematic::new(&["007<...", "..&..12"]).unwrap();
+        let rule = GearRule {
+            symbols: vec!['&'],
+            neighbours: crate::day_03::NeighbourCount::AtLeast(1),
+            reduction: Reduction::Sum,
+        };
+        let rendered = render(&schematic, rule, Format::Html);
+
+        assert!(rendered.starts_with(HTML_STYLE));
+        assert!(rendered.ends_with("</pre>\n"));
+        assert!(rendered.contains(
+            "<pre class=\"schematic\"><span class=\"part\">007</span><span class=\"symbol\">&lt;</span>...\n"
+        ));
+        assert!(rendered.contains(
+            "..<span class=\"gear\">&amp;</span>..<span class=\"orphan\">12</span>  \
+             <span class=\"ratio\">&amp; at (2, 1) = 7 (sum of 7)</span>\n"
+        ));
+    }
+}
@@ -0,0 +1,454 @@
+use std::collections::BTreeMap;
+
+use nom::error::ErrorKind;
+use unicode_width::UnicodeWidthChar;
+
+use super::gear::{Gear, GearRule};
+use crate::error::AocError;
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Dot,
+    Symbol(char),
+    /// A digit, only until the numbers are read.
+    Digit(u8),
+    /// A digit of the number at this index of the numbers of the schematic.
+    Number(u32),
+}
+
+impl Cell {
+    /// How many terminal columns the cell takes when printed.
+    fn width(&self) -> usize {
+        match self {
+            Cell::Symbol(symbol) => symbol.width().unwrap_or(0),
+            _ => 1,
+        }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(c: char) -> Self {
+        match c {
+            '0'..='9' => Cell::Digit(c as u8 - b'0'),
+            '.' => Cell::Dot,
+            c => Cell::Symbol(c),
+        }
+    }
+}
+
+/// A number of `len` digits, written from the character `offset` of `line`.
+///
+/// Positions count characters, so a symbol is a single cell however it is encoded, and
+/// `column` is where the number shows up in a terminal, past symbols that take no column
+/// or two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: isize,
+    pub line: usize,
+    pub offset: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+impl Number {
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.offset..self.offset + self.len).map(|x| (x, self.line))
+    }
+}
+
+/// The numbers next to every occurrence of a symbol.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolSummary {
+    pub occurrences: usize,
+    /// The values of the numbers next to each occurrence, so a number next to two
+    /// occurrences is there twice.
+    pub part_numbers: Vec<isize>,
+}
+
+/// An engine schematic where every cell knows the number written over it, so what is
+/// around a cell or a number is found by looking at its neighbours only.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    cells: Grid<Cell>,
+    numbers: Vec<Number>,
+}
+
+impl Schematic {
+    pub fn new(lines: &[&str]) -> Result<Self, AocError> {
+        let mut cells = Grid::parse_lines(lines.iter().copied(), |c| Some(Cell::from(c)))?;
+        let numbers = read_numbers(&mut cells)?;
+
+        Ok(Schematic { cells, numbers })
+    }
+
+    /// The width and height of the schematic, in characters.
+    pub fn size(&self) -> (usize, usize) {
+        self.cells.size()
+    }
+
+    /// Every number, line by line from left to right.
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    /// The position of every symbol, line by line from left to right.
+    pub fn symbols(&self) -> impl Iterator<Item = ((usize, usize), char)> + '_ {
+        self.cells.iter().filter_map(|(position, cell)| match cell {
+            Cell::Symbol(symbol) => Some((position, *symbol)),
+            _ => None,
+        })
+    }
+
+    /// The terminal column at which the cell at `position` is printed.
+    pub fn display_column(&self, (x, y): (usize, usize)) -> usize {
+        self.cells
+            .row(y)
+            .map(|row| row[..x].iter().map(Cell::width).sum())
+            .unwrap_or(0)
+    }
+
+    /// The numbers next to `position`, diagonals included, in the order they are written.
+    pub fn get_numbers_around(&self, position: (usize, usize)) -> Vec<&Number> {
+        let mut indices = self
+            .cells
+            .surrounding(position)
+            .filter_map(|(_, cell)| match cell {
+                Cell::Number(i) => Some(*i as usize),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+
+        indices.into_iter().map(|i| &self.numbers[i]).collect()
+    }
+
+    /// The symbols next to any digit of `number`, diagonals included.
+    pub fn get_symbols_around(&self, number: &Number) -> Vec<((usize, usize), char)> {
+        let (width, height) = self.cells.size();
+        let columns = number.offset.saturating_sub(1)..(number.offset + number.len + 1).min(width);
+        let lines = number.line.saturating_sub(1)..(number.line + 2).min(height);
+
+        lines
+            .flat_map(|y| columns.clone().map(move |x| (x, y)))
+            .filter_map(|position| match self.cells[position] {
+                Cell::Symbol(symbol) => Some((position, symbol)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The gears of the puzzle: `*` symbols next to exactly two numbers.
+    pub fn gears(&self) -> impl Iterator<Item = Gear<'_>> {
+        self.gears_with(GearRule::puzzle())
+    }
+
+    /// The symbols that are gears by `rule`.
+    pub fn gears_with(&self, rule: GearRule) -> impl Iterator<Item = Gear<'_>> {
+        self.symbols().filter_map(move |(position, symbol)| {
+            let numbers = self.get_numbers_around(position);
+
+            rule.is_gear(symbol, &numbers).then_some(Gear {
+                position,
+                symbol,
+                numbers,
+            })
+        })
+    }
+
+    /// Every symbol with the numbers next to it.
+    pub fn symbol_report(&self) -> BTreeMap<char, SymbolSummary> {
+        let mut report = BTreeMap::<char, SymbolSummary>::new();
+
+        for (position, symbol) in self.symbols() {
+            let summary = report.entry(symbol).or_default();
+            summary.occurrences += 1;
+            summary.part_numbers.extend(
+                self.get_numbers_around(position)
+                    .into_iter()
+                    .map(|n| n.value),
+            );
+        }
+
+        report
+    }
+
+    pub fn sum_part_numbers(&self) -> isize {
+        self.numbers
+            .iter()
+            .filter(|n| !self.get_symbols_around(n).is_empty())
+            .map(|n| n.value)
+            .sum()
+    }
+
+    pub fn sum_gear_ratios(&self) -> isize {
+        self.sum_gears(GearRule::puzzle())
+    }
+
+    /// Sums the values of the gears by `rule`.
+    pub fn sum_gears(&self, rule: GearRule) -> isize {
+        let reduction = rule.reduction;
+
+        self.gears_with(rule)
+            .map(|gear| gear.reduce(reduction))
+            .sum()
+    }
+}
+
+/// Reads the runs of digits of every line as numbers, and writes over their digits where
+/// they are in the numbers.
+fn read_numbers(cells: &mut Grid<Cell>) -> Result<Vec<Number>, AocError> {
+    let mut numbers = Vec::new();
+
+    for line in 0..cells.height() {
+        let row = cells.row(line).unwrap_or_default();
+        let first_number = numbers.len();
+
+        let mut offset = 0;
+        let mut column = 0;
+        while offset < row.len() {
+            let len = row[offset..]
+                .iter()
+                .take_while(|cell| matches!(cell, Cell::Digit(_)))
+                .count();
+
+            if len == 0 {
+                column += row[offset].width();
+                offset += 1;
+                continue;
+            }
+
+            let value = row[offset..offset + len]
+                .iter()
+                .try_fold(0isize, |value, cell| match cell {
+                    Cell::Digit(d) => value.checked_mul(10)?.checked_add(*d as isize),
+                    _ => Some(value),
+                })
+                .ok_or(AocError::Parse {
+                    line: line + 1,
+                    column: offset + 1,
+                    kind: ErrorKind::TooLarge,
+                })?;
+
+            numbers.push(Number {
+                value,
+                line,
+                offset,
+                column,
+                len,
+            });
+            offset += len;
+            column += len;
+        }
+
+        for (i, number) in numbers.iter().enumerate().skip(first_number) {
+            let index = u32::try_from(i).map_err(|_| {
+                AocError::Invalid(format!("there are more than {} numbers", u32::MAX))
+            })?;
+
+            for position in number.positions() {
+                cells[position] = Cell::Number(index);
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::gear::{NeighbourCount, Reduction};
+    use super::*;
+
+    const LINES: [&str; 5] = [
+        "467..114..",
+        "...*......",
+        "..35..633.",
+        "......#...",
+        "617*......",
+    ];
+
+    fn values(numbers: Vec<&Number>) -> Vec<isize> {
+        numbers.into_iter().map(|n| n.value).collect()
+    }
+
+    #[test]
+    fn test_numbers() {
+        let schematic = Schematic::new(&LINES[..3]).unwrap();
+
+        assert_eq!(
+            schematic
+                .numbers()
+                .iter()
+                .map(|n| (n.value, n.offset, n.line))
+                .collect::<Vec<_>>(),
+            vec![(467, 0, 0), (114, 5, 0), (35, 2, 2), (633, 6, 2)]
+        );
+        assert_eq!(schematic.cells[(1, 0)], Cell::Number(0));
+        assert_eq!(schematic.cells[(8, 2)], Cell::Number(3));
+    }
+
+    #[test]
+    fn test_numbers_around() {
+        let schematic = Schematic::new(&LINES).unwrap();
+
+        assert_eq!(values(schematic.get_numbers_around((3, 1))), vec![467, 35]);
+        assert_eq!(values(schematic.get_numbers_around((6, 3))), vec![633]);
+        assert_eq!(values(schematic.get_numbers_around((3, 4))), vec![617]);
+        assert_eq!(values(schematic.get_numbers_around((9, 4))), vec![]);
+    }
+
+    #[test]
+    fn test_symbols_around() {
+        let schematic = Schematic::new(&LINES).unwrap();
+        let numbers = schematic.numbers();
+
+        assert_eq!(
+            schematic.get_symbols_around(&numbers[0]),
+            vec![((3, 1), '*')]
+        );
+        assert_eq!(schematic.get_symbols_around(&numbers[1]), vec![]);
+        assert_eq!(
+            schematic.get_symbols_around(&numbers[2]),
+            vec![((3, 1), '*')]
+        );
+        assert_eq!(
+            schematic.get_symbols_around(&numbers[3]),
+            vec![((6, 3), '#')]
+        );
+        assert_eq!(
+            schematic.get_symbols_around(&numbers[4]),
+            vec![((3, 4), '*')]
+        );
+    }
+
+    #[test]
+    fn test_gears() {
+        let schematic = Schematic::new(&LINES).unwrap();
+        let gears = schematic.gears().collect::<Vec<_>>();
+
+        assert_eq!(gears.len(), 1);
+        assert_eq!(gears[0].position, (3, 1));
+        assert_eq!(gears[0].ratio(), 467 * 35);
+        assert_eq!(
+            schematic.symbols().collect::<Vec<_>>(),
+            vec![((3, 1), '*'), ((6, 3), '#'), ((3, 4), '*')]
+        );
+    }
+
+    #[test]
+    fn test_gear_rules() {
+        let schematic = Schematic::new(&LINES).unwrap();
+        let positions = |rule: GearRule| {
+            schematic
+                .gears_with(rule)
+                .map(|gear| gear.position)
+                .collect::<Vec<_>>()
+        };
+
+        let rule = GearRule {
+            symbols: vec!['*', '#'],
+            neighbours: NeighbourCount::AtLeast(1),
+            reduction: Reduction::Sum,
+        };
+        assert_eq!(positions(rule.clone()), vec![(3, 1), (6, 3), (3, 4)]);
+        assert_eq!(schematic.sum_gears(rule), 467 + 35 + 633 + 617);
+
+        let rule = GearRule {
+            symbols: vec!['*'],
+            neighbours: NeighbourCount::AtMost(1),
+            reduction: Reduction::Max,
+        };
+        assert_eq!(positions(rule.clone()), vec![(3, 4)]);
+        assert_eq!(schematic.sum_gears(rule), 617);
+
+        assert_eq!(schematic.sum_gears(GearRule::puzzle()), 467 * 35);
+    }
+
+    #[test]
+    fn test_symbol_report() {
+        let schematic = Schematic::new(&LINES).unwrap();
+
+        assert_eq!(
+            schematic.symbol_report().into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    '#',
+                    SymbolSummary {
+                        occurrences: 1,
+                        part_numbers: vec![633]
+                    }
+                ),
+                (
+                    '*',
+                    SymbolSummary {
+                        occurrences: 2,
+                        part_numbers: vec![467, 35, 617]
+                    }
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unicode_symbols() {
+        let schematic = Schematic::new(&["12✱..", "..€34", "5＊6é.", "..\u{301}78"]).unwrap();
+
+        assert_eq!(
+            schematic.symbols().collect::<Vec<_>>(),
+            vec![
+                ((2, 0), '✱'),
+                ((2, 1), '€'),
+                ((1, 2), '＊'),
+                ((3, 2), 'é'),
+                ((2, 3), '\u{301}')
+            ]
+        );
+        assert_eq!(
+            schematic
+                .numbers()
+                .iter()
+                .map(|n| (n.value, n.offset, n.column))
+                .collect::<Vec<_>>(),
+            vec![(12, 0, 0), (34, 3, 3), (5, 0, 0), (6, 2, 3), (78, 3, 2)]
+        );
+        assert_eq!(values(schematic.get_numbers_around((2, 0))), vec![12, 34]);
+        assert_eq!(
+            values(schematic.get_numbers_around((2, 1))),
+            vec![12, 34, 6]
+        );
+        assert_eq!(schematic.display_column((4, 2)), 5);
+        assert_eq!(schematic.sum_part_numbers(), 12 + 34 + 5 + 6 + 78);
+
+        let rule = GearRule {
+            symbols: vec!['€', '＊'],
+            ..GearRule::puzzle()
+        };
+        assert_eq!(schematic.sum_gears(rule), 5 * 6);
+    }
+
+    #[test]
+    fn test_unicode_errors() {
+        assert_eq!(
+            Schematic::new(&["✱✱1", "..."]).map(|s| s.sum_part_numbers()),
+            Ok(1)
+        );
+        assert!(matches!(
+            Schematic::new(&["✱✱1", "...."]),
+            Err(AocError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_number_too_large() {
+        assert_eq!(
+            Schematic::new(&["99999999999999999999*"]).err(),
+            Some(AocError::Parse {
+                line: 1,
+                column: 1,
+                kind: ErrorKind::TooLarge
+            })
+        );
+    }
+}
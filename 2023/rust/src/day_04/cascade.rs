@@ -0,0 +1,161 @@
+use crate::error::AocError;
+
+use super::Card;
+
+/// What happens to the copies a card wins of cards past the last one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// They are not won.
+    Clamp,
+    /// The cards are refused, as the puzzle promises it never happens.
+    Error,
+}
+
+/// How many copies there are of a card and where they come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardTally {
+    pub id: usize,
+    /// The original card and the copies won.
+    pub copies: usize,
+    /// The id of every card that won copies of this one, with how many it won, in order.
+    pub won_from: Vec<(usize, usize)>,
+}
+
+impl CardTally {
+    pub fn won(&self) -> usize {
+        self.copies - 1
+    }
+}
+
+/// The copies there are of every card, in order.
+///
+/// A card wins one copy of each of the next cards for every copy there is of it, so the
+/// copies won of a card are the sum of the copies of the cards whose ranges cover it. The
+/// copies are added to a running sum where a range starts and taken off where it ends, so
+/// every card is only looked at once.
+pub fn count_copies(cards: &[Card], overflow: Overflow) -> Result<Vec<usize>, AocError> {
+    let mut ending = vec![0; cards.len() + 1];
+    let mut won = 0;
+    let mut copies = Vec::with_capacity(cards.len());
+
+    for (i, card) in cards.iter().enumerate() {
+        won -= ending[i];
+        let count = 1 + won;
+        copies.push(count);
+
+        let end = won_range_end(cards, i, card, overflow)?;
+        won += count;
+        ending[end] += count;
+    }
+
+    Ok(copies)
+}
+
+/// The copies of every card, with the cards that won them.
+pub fn trace(cards: &[Card], overflow: Overflow) -> Result<Vec<CardTally>, AocError> {
+    let copies = count_copies(cards, overflow)?;
+    let mut tallies = cards
+        .iter()
+        .zip(&copies)
+        .map(|(card, &copies)| CardTally {
+            id: card.get_id(),
+            copies,
+            won_from: vec![],
+        })
+        .collect::<Vec<_>>();
+
+    for (i, card) in cards.iter().enumerate() {
+        let end = won_range_end(cards, i, card, overflow)?;
+        for tally in &mut tallies[i + 1..end] {
+            tally.won_from.push((card.get_id(), copies[i]));
+        }
+    }
+
+    Ok(tallies)
+}
+
+/// Where the cards that `card`, at index `i`, wins copies of end.
+fn won_range_end(
+    cards: &[Card],
+    i: usize,
+    card: &Card,
+    overflow: Overflow,
+) -> Result<usize, AocError> {
+    let end = i + 1 + card.get_total_matching_numbers();
+    if overflow == Overflow::Error && end > cards.len() {
+        return Err(past_the_end(card.get_id(), end - cards.len()));
+    }
+
+    Ok(end.min(cards.len()))
+}
+
+pub(super) fn past_the_end(id: usize, cards: usize) -> AocError {
+    AocError::Invalid(format!(
+        "card {} wins copies of {} cards past the last one",
+        id, cards
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::CardReader;
+    use super::*;
+
+    const TEST_INPUT_01: &str = include_str!("../day_04/test-input-01.txt");
+
+    fn cards(input: &str) -> Vec<Card> {
+        let mut reader = CardReader::new();
+
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| reader.read(i + 1, line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_count_copies() {
+        assert_eq!(
+            count_copies(&cards(TEST_INPUT_01), Overflow::Error),
+            Ok(vec![1, 2, 4, 8, 14, 1])
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let tallies = trace(&cards(TEST_INPUT_01), Overflow::Error).unwrap();
+
+        assert_eq!(
+            tallies[3],
+            CardTally {
+                id: 4,
+                copies: 8,
+                won_from: vec![(1, 1), (2, 2), (3, 4)]
+            }
+        );
+        assert_eq!(tallies[5].won_from, vec![]);
+        for tally in &tallies {
+            assert_eq!(
+                tally.won(),
+                tally.won_from.iter().map(|(_, copies)| copies).sum()
+            );
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let cards = cards("Card 1: 1 2 | 1 2\nCard 2: 3 4 | 3 5");
+
+        assert_eq!(
+            count_copies(&cards, Overflow::Error),
+            Err(AocError::Invalid(
+                "card 1 wins copies of 1 cards past the last one".to_string()
+            ))
+        );
+        assert_eq!(count_copies(&cards, Overflow::Clamp), Ok(vec![1, 2]));
+        assert_eq!(
+            trace(&cards, Overflow::Clamp).unwrap()[1].won_from,
+            vec![(1, 1)]
+        );
+    }
+}
@@ -1,4 +1,9 @@
-use std::collections::{HashMap, HashSet};
+mod cascade;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::io::BufRead;
+use std::ops::RangeInclusive;
 
 use nom::bytes::complete::tag;
 use nom::character::complete::{multispace0, multispace1, u32};
@@ -6,51 +11,208 @@ use nom::multi::separated_list0;
 use nom::sequence::preceded;
 use nom::IResult;
 
-pub fn part_1(input: &str) -> usize {
-    let cards = get_cards(input);
-    let points: usize = cards.iter().map(|c| c.get_points()).sum();
+use crate::error::{parse_all, AocError};
+use crate::input::for_each_line;
+use crate::solution::{Answer, Solution, Streamed, Streaming};
+
+pub use cascade::{count_copies, trace, CardTally, Overflow};
+
+/// Sums the points of the cards read from `reader`, one card at a time.
+pub fn part_1<R: BufRead>(reader: R) -> Result<usize, AocError> {
+    sum_points(reader, &mut CardReader::new())
+}
+
+/// Sums the points of the cards read from `reader` with `cards`, which keeps the warnings
+/// about them.
+pub fn sum_points<R: BufRead>(reader: R, cards: &mut CardReader) -> Result<usize, AocError> {
+    let mut total = 0;
+    for_each_line(reader, |i, line| {
+        total += cards.read(i, line)?.get_points();
+
+        Ok(())
+    })?;
+
+    Ok(total)
+}
+
+/// Counts the scratchcards won from the cards read from `reader`, one card at a time. Cards
+/// only win copies of the cards right after them, so only the copies won of the next few
+/// cards are kept.
+pub fn part_2<R: BufRead>(reader: R) -> Result<usize, AocError> {
+    count_scratchcards(reader, &mut CardReader::new())
+}
+
+/// Counts the scratchcards won from the cards read from `reader` with `cards`, which keeps
+/// the warnings about them.
+pub fn count_scratchcards<R: BufRead>(
+    reader: R,
+    cards: &mut CardReader,
+) -> Result<usize, AocError> {
+    let mut won_copies: VecDeque<usize> = VecDeque::new();
+    let mut cards_read = 0;
+    // the cards that win copies of cards further than every card before them, with how
+    // many cards that is, while it is past the cards read so far
+    let mut reaching: VecDeque<(usize, usize)> = VecDeque::new();
+    let mut total = 0;
+
+    for_each_line(reader, |i, line| {
+        let card = cards.read(i, line)?;
+        let count = 1 + won_copies.pop_front().unwrap_or(0);
+        total += count;
+
+        let matching = card.get_total_matching_numbers();
+        if won_copies.len() < matching {
+            won_copies.resize(matching, 0);
+        }
+        for copies in won_copies.iter_mut().take(matching) {
+            *copies += count;
+        }
+        cards_read += 1;
+        while reaching
+            .front()
+            .is_some_and(|&(_, reach)| reach <= cards_read)
+        {
+            reaching.pop_front();
+        }
+        let reach = cards_read + matching;
+        if reach > reaching.back().map_or(cards_read, |&(_, reach)| reach) {
+            reaching.push_back((card.get_id(), reach));
+        }
+
+        Ok(())
+    })?;
+
+    // the first card to win copies past the last one, as when the cards are all kept
+    if let Some(&(id, reach)) = reaching.front() {
+        return Err(cascade::past_the_end(id, reach - cards_read));
+    }
+
+    Ok(total)
+}
 
-    points
+pub struct Day04 {
+    cards: Vec<Card>,
+    warnings: Vec<String>,
 }
 
-pub fn part_2(input: &str) -> usize {
-    let cards = get_cards(input);
-    let mut card_counter = CardCounter::new(&cards);
+impl Solution for Day04 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let mut reader = CardReader::new();
+        let cards = get_cards(&mut reader, input)?;
 
-    for card in &cards {
-        card_counter.process_card(card);
+        Ok(Day04 {
+            cards,
+            warnings: reader.warnings,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(get_total_points(&self.cards)))
     }
 
-    card_counter.get_total_count()
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(get_total_scratchcards(&self.cards)?))
+    }
+
+    fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
 }
 
-fn get_cards(input: &str) -> Vec<Card> {
+impl Streaming for Day04 {
+    fn stream_part_one(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_1(reader)?))
+    }
+
+    fn stream_part_two(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_2(reader)?))
+    }
+
+    fn stream_part(reader: &mut dyn BufRead, part: u8) -> Result<Streamed, AocError> {
+        let mut cards = CardReader::new();
+        let answer: Answer = match part {
+            1 => Box::new(sum_points(reader, &mut cards)?),
+            _ => Box::new(count_scratchcards(reader, &mut cards)?),
+        };
+
+        Ok((answer, cards.warnings))
+    }
+}
+
+fn get_total_points(cards: &[Card]) -> usize {
+    cards.iter().map(|c| c.get_points()).sum()
+}
+
+fn get_total_scratchcards(cards: &[Card]) -> Result<usize, AocError> {
+    Ok(count_copies(cards, Overflow::Error)?.into_iter().sum())
+}
+
+fn get_cards(reader: &mut CardReader, input: &str) -> Result<Vec<Card>, AocError> {
     input
         .lines()
-        .map(|line| {
-            let parse_line_result = parse_line(line);
-
-            match parse_line_result {
-                Ok((_, card)) => match card {
-                    Ok(card) => card,
-                    Err(message) => panic!("could not build card: {}", message),
-                },
-                Err(message) => panic!("could not parse line: {}", message),
-            }
-        })
+        .enumerate()
+        .map(|(i, line)| reader.read(i + 1, line))
         .collect()
 }
 
-fn parse_line(input: &str) -> IResult<&str, Result<Card, String>> {
+/// Reads cards one line at a time and checks them against a shape, which is the shape of
+/// the first card unless one is given.
+#[derive(Debug, Default)]
+pub struct CardReader {
+    shape: Option<CardShape>,
+    warnings: Vec<String>,
+}
+
+impl CardReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_shape(shape: CardShape) -> Self {
+        Self {
+            shape: Some(shape),
+            warnings: vec![],
+        }
+    }
+
+    pub fn read(&mut self, line_number: usize, line: &str) -> Result<Card, AocError> {
+        let card_builder = parse_all(line, parse_line).map_err(|e| e.on_line(line_number))?;
+        let shape = self.shape.get_or_insert_with(|| {
+            let (winning, received) = card_builder.counts();
+            CardShape::of(winning, received)
+        });
+
+        self.warnings.extend(
+            card_builder
+                .warnings()
+                .into_iter()
+                .map(|warning| format!("line {}: {}", line_number, warning)),
+        );
+
+        card_builder
+            .with_shape(shape.clone())
+            .build()
+            .map_err(|violations| {
+                AocError::Invalid(format!("line {}: {}", line_number, violations.join(", ")))
+            })
+    }
+
+    /// What looked wrong in the cards read so far, such as numbers written twice.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
+
+fn parse_line(input: &str) -> IResult<&str, CardBuilder> {
     let (input, id) = pase_card_id(input)?;
-    let mut card_builder = CardBuilder::new(id as usize);
+    let mut card_builder = CardBuilder::new(id);
     let (input, _) = tag(": ")(input)?;
     let (input, winning_numbers) = parse_winning_numbers(input)?;
     card_builder.add_winning_numbers(winning_numbers);
     let (input, received_numbers) = parse_received_numbers(input)?;
     card_builder.add_received_numbers(received_numbers);
 
-    Ok((input, card_builder.build()))
+    Ok((input, card_builder))
 }
 
 fn pase_card_id(input: &str) -> IResult<&str, usize> {
@@ -80,24 +242,115 @@ fn parse_numbers(input: &str) -> IResult<&str, Vec<usize>> {
     Ok((input, numbers))
 }
 
+/// How many winning and received numbers a card may have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardShape {
+    Unrestricted,
+    /// One of the given counts of each.
+    Fixed {
+        winning: Vec<usize>,
+        received: Vec<usize>,
+    },
+    Ranges {
+        winning: RangeInclusive<usize>,
+        received: RangeInclusive<usize>,
+    },
+}
+
+impl CardShape {
+    /// 5 winning and 8 received numbers, as in the example, or 10 and 25, as in the inputs.
+    pub fn puzzle() -> Self {
+        CardShape::Fixed {
+            winning: vec![5, 10],
+            received: vec![8, 25],
+        }
+    }
+
+    /// Exactly `winning` winning numbers and `received` received numbers.
+    pub fn of(winning: usize, received: usize) -> Self {
+        CardShape::Fixed {
+            winning: vec![winning],
+            received: vec![received],
+        }
+    }
+
+    /// Everything wrong with the counts of a card of this shape.
+    pub fn check(&self, winning: usize, received: usize) -> Vec<String> {
+        let violation = |expected: String, kind: &str, count: usize| {
+            format!(
+                "there should be {} {} numbers but there are {}",
+                expected, kind, count
+            )
+        };
+
+        match self {
+            CardShape::Unrestricted => vec![],
+            CardShape::Fixed {
+                winning: winning_counts,
+                received: received_counts,
+            } => {
+                let expected = |counts: &[usize]| {
+                    counts
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" or ")
+                };
+
+                [
+                    (winning_counts, "winning", winning),
+                    (received_counts, "received", received),
+                ]
+                .into_iter()
+                .filter(|(counts, _, count)| !counts.contains(count))
+                .map(|(counts, kind, count)| violation(expected(counts), kind, count))
+                .collect()
+            }
+            CardShape::Ranges {
+                winning: winning_range,
+                received: received_range,
+            } => [
+                (winning_range, "winning", winning),
+                (received_range, "received", received),
+            ]
+            .into_iter()
+            .filter(|(range, _, count)| !range.contains(count))
+            .map(|(range, kind, count)| {
+                let expected = format!("{} to {}", range.start(), range.end());
+                violation(expected, kind, count)
+            })
+            .collect(),
+        }
+    }
+}
+
+/// Collects the numbers of a card as they are written, so numbers written twice can be
+/// told apart from the numbers of the card.
 #[derive(Debug)]
 pub struct CardBuilder {
     id: usize,
-    winning_numbers: HashSet<usize>,
-    received_numbers: HashSet<usize>,
+    shape: CardShape,
+    winning_numbers: Vec<usize>,
+    received_numbers: Vec<usize>,
 }
 
 impl CardBuilder {
     pub fn new(id: usize) -> Self {
         Self {
             id,
-            winning_numbers: HashSet::new(),
-            received_numbers: HashSet::new(),
+            shape: CardShape::puzzle(),
+            winning_numbers: vec![],
+            received_numbers: vec![],
         }
     }
 
+    pub fn with_shape(mut self, shape: CardShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
     pub fn add_winning_number(&mut self, number: usize) {
-        self.winning_numbers.insert(number);
+        self.winning_numbers.push(number);
     }
 
     pub fn add_winning_numbers(&mut self, numbers: Vec<usize>) {
@@ -105,37 +358,72 @@ impl CardBuilder {
     }
 
     pub fn add_received_number(&mut self, number: usize) {
-        self.received_numbers.insert(number);
+        self.received_numbers.push(number);
     }
 
     pub fn add_received_numbers(&mut self, numbers: Vec<usize>) {
         self.received_numbers.extend(numbers);
     }
 
-    pub fn build(self) -> Result<Card, String> {
-        if self.winning_numbers.len() != 5 && self.winning_numbers.len() != 10 {
-            return Err(format!(
-                "there should be 5 or 10 winning numbers but there are {}",
-                self.winning_numbers.len()
-            ));
-        }
+    /// The numbers written more than once, which only count once.
+    pub fn warnings(&self) -> Vec<String> {
+        let duplicates = |numbers: &[usize], kind: &str| {
+            let mut counts = HashMap::<usize, usize>::new();
+            for &number in numbers {
+                *counts.entry(number).or_default() += 1;
+            }
+
+            let mut duplicates = counts
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .collect::<Vec<_>>();
+            duplicates.sort_unstable();
+
+            duplicates
+                .into_iter()
+                .map(|(number, count)| {
+                    format!(
+                        "{} number {} is written {} times on card {}",
+                        kind, number, count, self.id
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut warnings = duplicates(&self.winning_numbers, "winning");
+        warnings.extend(duplicates(&self.received_numbers, "received"));
+
+        warnings
+    }
+
+    /// How many different winning and received numbers there are, as a number written twice
+    /// only counts once.
+    fn counts(&self) -> (usize, usize) {
+        let distinct = |numbers: &[usize]| numbers.iter().collect::<HashSet<_>>().len();
 
-        if self.received_numbers.len() != 8 && self.received_numbers.len() != 25 {
-            return Err(format!(
-                "there should be 8 or 25 received numbers but there are {}",
-                self.received_numbers.len(),
-            ));
+        (
+            distinct(&self.winning_numbers),
+            distinct(&self.received_numbers),
+        )
+    }
+
+    /// Builds the card, or gives every way in which it does not have the expected shape.
+    pub fn build(self) -> Result<Card, Vec<String>> {
+        let (winning, received) = self.counts();
+        let violations = self.shape.check(winning, received);
+        if !violations.is_empty() {
+            return Err(violations);
         }
 
         Ok(Card {
             id: self.id,
-            winning_numbers: self.winning_numbers,
-            received_numbers: self.received_numbers,
+            winning_numbers: self.winning_numbers.into_iter().collect(),
+            received_numbers: self.received_numbers.into_iter().collect(),
         })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Card {
     id: usize,
     winning_numbers: HashSet<usize>,
@@ -164,69 +452,25 @@ impl Card {
     }
 }
 
-struct CardCount {
-    count: usize,
-}
-
-impl CardCount {
-    fn new() -> Self {
-        Self { count: 1 }
-    }
-
-    fn increase_count_by(&mut self, amount: usize) {
-        self.count += amount;
-    }
-}
-
-struct CardCounter {
-    counts: HashMap<usize, CardCount>,
-}
-
-impl CardCounter {
-    fn new(cards: &Vec<Card>) -> Self {
-        let counts = cards
-            .iter()
-            .map(|card| (card.get_id(), CardCount::new()))
-            .collect();
-
-        Self { counts }
-    }
-
-    fn get_total_count(&self) -> usize {
-        let mut total = 0;
-        for card_count in self.counts.values() {
-            total += card_count.count;
-        }
-        total
-    }
-
-    fn get_count(&self, id: usize) -> usize {
-        if let Some(card_count) = self.counts.get(&id) {
-            card_count.count
-        } else {
-            0
-        }
-    }
-
-    fn process_card(&mut self, card: &Card) {
-        let id = card.get_id();
-        let total_matching_numbers = card.get_total_matching_numbers();
+/// Writes the card as a line of the puzzle input, with its numbers sorted and aligned.
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sorted = |numbers: &HashSet<usize>| {
+            let mut numbers = numbers.iter().copied().collect::<Vec<_>>();
+            numbers.sort_unstable();
+            numbers
+        };
 
-        if total_matching_numbers == 0 {
-            return;
+        write!(f, "Card {}:", self.id)?;
+        for number in sorted(&self.winning_numbers) {
+            write!(f, " {:>2}", number)?;
         }
-
-        for n in (id + 1)..=(id + total_matching_numbers) {
-            self.increase_count(n, self.get_count(id));
+        write!(f, " |")?;
+        for number in sorted(&self.received_numbers) {
+            write!(f, " {:>2}", number)?;
         }
-    }
 
-    fn increase_count(&mut self, id: usize, amount: usize) {
-        if let Some(card_count) = self.counts.get_mut(&id) {
-            card_count.increase_count_by(amount);
-        } else {
-            self.counts.insert(id, CardCount::new());
-        }
+        Ok(())
     }
 }
 
@@ -234,8 +478,17 @@ impl CardCounter {
 mod tests {
 
     use super::*;
+    use crate::generate::generate;
+    use proptest::prelude::*;
+
     const TEST_INPUT_01: &str = include_str!("../day_04/test-input-01.txt");
-    const COMPLETE_INPUT_01: &str = include_str!("../day_04/input-01.txt");
+    const INPUT_01: &str = include_str!("../day_04/input-01.txt");
+
+    fn assert_display_round_trip(input: &str) {
+        for card in get_cards(&mut CardReader::new(), input).unwrap() {
+            assert_eq!(CardReader::new().read(1, &card.to_string()), Ok(card));
+        }
+    }
 
     #[test]
     fn test_card_builder_adds_one_winning_number() {
@@ -243,14 +496,8 @@ mod tests {
         card_builder.add_winning_number(1);
 
         assert_eq!(card_builder.id, 1);
-        assert_eq!(
-            card_builder.winning_numbers,
-            vec![1].into_iter().collect::<HashSet<usize>>()
-        );
-        assert_eq!(
-            card_builder.received_numbers,
-            vec![].into_iter().collect::<HashSet<usize>>()
-        );
+        assert_eq!(card_builder.winning_numbers, vec![1]);
+        assert_eq!(card_builder.received_numbers, vec![]);
 
         let card = card_builder.build();
         match card {
@@ -258,7 +505,10 @@ mod tests {
             Err(message) => {
                 assert_eq!(
                     message,
-                    "there should be 5 or 10 winning numbers but there are 1"
+                    vec![
+                        "there should be 5 or 10 winning numbers but there are 1",
+                        "there should be 8 or 25 received numbers but there are 0"
+                    ]
                 )
             }
         }
@@ -270,14 +520,8 @@ mod tests {
         card_builder.add_winning_numbers(vec![1, 2, 3]);
 
         assert_eq!(card_builder.id, 1);
-        assert_eq!(
-            card_builder.winning_numbers,
-            vec![1, 2, 3].into_iter().collect::<HashSet<usize>>()
-        );
-        assert_eq!(
-            card_builder.received_numbers,
-            vec![].into_iter().collect::<HashSet<usize>>()
-        );
+        assert_eq!(card_builder.winning_numbers, vec![1, 2, 3]);
+        assert_eq!(card_builder.received_numbers, vec![]);
 
         let card = card_builder.build();
         match card {
@@ -285,36 +529,28 @@ mod tests {
             Err(message) => {
                 assert_eq!(
                     message,
-                    "there should be 5 or 10 winning numbers but there are 3"
+                    vec![
+                        "there should be 5 or 10 winning numbers but there are 3",
+                        "there should be 8 or 25 received numbers but there are 0"
+                    ]
                 )
             }
         }
     }
 
     #[test]
-    fn test_card_builder_does_not_add_duplicate_winning_numbers() {
+    fn test_card_builder_warns_of_duplicate_winning_numbers() {
         let mut card_builder = CardBuilder::new(1);
         card_builder.add_winning_numbers(vec![1, 2, 3]);
+        assert_eq!(card_builder.warnings(), Vec::<String>::new());
 
-        assert_eq!(card_builder.id, 1);
-        assert_eq!(
-            card_builder.winning_numbers,
-            vec![1, 2, 3].into_iter().collect::<HashSet<usize>>()
-        );
         card_builder.add_winning_number(1);
+        assert_eq!(card_builder.winning_numbers, vec![1, 2, 3, 1]);
         assert_eq!(
-            card_builder.winning_numbers,
-            vec![1, 2, 3].into_iter().collect::<HashSet<usize>>()
-        );
-        card_builder.add_winning_numbers(vec![2, 3]);
-        assert_eq!(
-            card_builder.winning_numbers,
-            vec![1, 2, 3].into_iter().collect::<HashSet<usize>>()
-        );
-        assert_eq!(
-            card_builder.received_numbers,
-            vec![].into_iter().collect::<HashSet<usize>>()
+            card_builder.warnings(),
+            vec!["winning number 1 is written 2 times on card 1"]
         );
+        assert_eq!(card_builder.received_numbers, vec![]);
 
         let card = card_builder.build();
         match card {
@@ -322,7 +558,10 @@ mod tests {
             Err(message) => {
                 assert_eq!(
                     message,
-                    "there should be 5 or 10 winning numbers but there are 3"
+                    vec![
+                        "there should be 5 or 10 winning numbers but there are 3",
+                        "there should be 8 or 25 received numbers but there are 0"
+                    ]
                 )
             }
         }
@@ -333,14 +572,8 @@ mod tests {
         let mut card_builder = CardBuilder::new(1);
         card_builder.add_received_number(1);
         assert_eq!(card_builder.id, 1);
-        assert_eq!(
-            card_builder.winning_numbers,
-            vec![].into_iter().collect::<HashSet<usize>>()
-        );
-        assert_eq!(
-            card_builder.received_numbers,
-            vec![1].into_iter().collect::<HashSet<usize>>()
-        );
+        assert_eq!(card_builder.winning_numbers, vec![]);
+        assert_eq!(card_builder.received_numbers, vec![1]);
 
         let card = card_builder.build();
         match card {
@@ -348,7 +581,10 @@ mod tests {
             Err(message) => {
                 assert_eq!(
                     message,
-                    "there should be 5 or 10 winning numbers but there are 0"
+                    vec![
+                        "there should be 5 or 10 winning numbers but there are 0",
+                        "there should be 8 or 25 received numbers but there are 1"
+                    ]
                 )
             }
         }
@@ -359,14 +595,8 @@ mod tests {
         let mut card_builder = CardBuilder::new(1);
         card_builder.add_received_numbers(vec![1, 2, 3]);
         assert_eq!(card_builder.id, 1);
-        assert_eq!(
-            card_builder.winning_numbers,
-            vec![].into_iter().collect::<HashSet<usize>>()
-        );
-        assert_eq!(
-            card_builder.received_numbers,
-            vec![1, 2, 3].into_iter().collect::<HashSet<usize>>()
-        );
+        assert_eq!(card_builder.winning_numbers, vec![]);
+        assert_eq!(card_builder.received_numbers, vec![1, 2, 3]);
 
         let card = card_builder.build();
         match card {
@@ -374,35 +604,28 @@ mod tests {
             Err(message) => {
                 assert_eq!(
                     message,
-                    "there should be 5 or 10 winning numbers but there are 0"
+                    vec![
+                        "there should be 5 or 10 winning numbers but there are 0",
+                        "there should be 8 or 25 received numbers but there are 3"
+                    ]
                 )
             }
         }
     }
 
     #[test]
-    fn test_card_builder_does_not_add_duplicate_received_numbers() {
+    fn test_card_builder_warns_of_duplicate_received_numbers() {
         let mut card_builder = CardBuilder::new(1);
         card_builder.add_received_numbers(vec![1, 2, 3]);
-        assert_eq!(card_builder.id, 1);
-        assert_eq!(
-            card_builder.winning_numbers,
-            vec![].into_iter().collect::<HashSet<usize>>()
-        );
-        assert_eq!(
-            card_builder.received_numbers,
-            vec![1, 2, 3].into_iter().collect::<HashSet<usize>>()
-        );
+        assert_eq!(card_builder.warnings(), Vec::<String>::new());
+
         card_builder.add_received_number(1);
+        assert_eq!(card_builder.received_numbers, vec![1, 2, 3, 1]);
         assert_eq!(
-            card_builder.received_numbers,
-            vec![1, 2, 3].into_iter().collect::<HashSet<usize>>()
-        );
-        card_builder.add_received_numbers(vec![2, 3]);
-        assert_eq!(
-            card_builder.received_numbers,
-            vec![1, 2, 3].into_iter().collect::<HashSet<usize>>()
+            card_builder.warnings(),
+            vec!["received number 1 is written 2 times on card 1"]
         );
+        assert_eq!(card_builder.winning_numbers, vec![]);
 
         let card = card_builder.build();
         match card {
@@ -410,7 +633,10 @@ mod tests {
             Err(message) => {
                 assert_eq!(
                     message,
-                    "there should be 5 or 10 winning numbers but there are 0"
+                    vec![
+                        "there should be 5 or 10 winning numbers but there are 0",
+                        "there should be 8 or 25 received numbers but there are 3"
+                    ]
                 )
             }
         }
@@ -428,7 +654,7 @@ mod tests {
             Err(message) => {
                 assert_eq!(
                     message,
-                    "there should be 5 or 10 winning numbers but there are 4"
+                    vec!["there should be 5 or 10 winning numbers but there are 4"]
                 )
             }
         }
@@ -446,7 +672,7 @@ mod tests {
             Err(message) => {
                 assert_eq!(
                     message,
-                    "there should be 8 or 25 received numbers but there are 7"
+                    vec!["there should be 8 or 25 received numbers but there are 7"]
                 )
             }
         }
@@ -461,7 +687,7 @@ mod tests {
         assert!(card.is_ok());
         let card = match card {
             Ok(card) => card,
-            Err(message) => panic!("could not build card: {}", message),
+            Err(message) => panic!("could not build card: {:?}", message),
         };
         assert_eq!(card.id, 1);
         assert_eq!(
@@ -485,7 +711,7 @@ mod tests {
         assert!(card.is_ok());
         let card = match card {
             Ok(card) => card,
-            Err(message) => panic!("could not build card: {}", message),
+            Err(message) => panic!("could not build card: {:?}", message),
         };
         assert_eq!(card.get_points(), 16);
 
@@ -496,7 +722,7 @@ mod tests {
         assert!(card.is_ok());
         let card = match card {
             Ok(card) => card,
-            Err(message) => panic!("could not build card: {}", message),
+            Err(message) => panic!("could not build card: {:?}", message),
         };
         assert_eq!(card.get_points(), 8);
 
@@ -507,7 +733,7 @@ mod tests {
         assert!(card.is_ok());
         let card = match card {
             Ok(card) => card,
-            Err(message) => panic!("could not build card: {}", message),
+            Err(message) => panic!("could not build card: {:?}", message),
         };
         assert_eq!(card.get_points(), 4);
 
@@ -518,7 +744,7 @@ mod tests {
         assert!(card.is_ok());
         let card = match card {
             Ok(card) => card,
-            Err(message) => panic!("could not build card: {}", message),
+            Err(message) => panic!("could not build card: {:?}", message),
         };
         assert_eq!(card.get_points(), 2);
 
@@ -529,7 +755,7 @@ mod tests {
         assert!(card.is_ok());
         let card = match card {
             Ok(card) => card,
-            Err(message) => panic!("could not build card: {}", message),
+            Err(message) => panic!("could not build card: {:?}", message),
         };
         assert_eq!(card.get_points(), 1);
 
@@ -540,7 +766,7 @@ mod tests {
         assert!(card.is_ok());
         let card = match card {
             Ok(card) => card,
-            Err(message) => panic!("could not build card: {}", message),
+            Err(message) => panic!("could not build card: {:?}", message),
         };
         assert_eq!(card.get_points(), 0);
     }
@@ -619,10 +845,10 @@ mod tests {
     #[test]
     fn test_parse_line_1() {
         let input = "Card 1: 1 2 3 4 5 | 1 2 3 4 5 6 7 8";
-        let (_, card) = parse_line(input).unwrap();
-        let card = match card {
+        let (_, card_builder) = parse_line(input).unwrap();
+        let card = match card_builder.build() {
             Ok(card) => card,
-            Err(message) => panic!("could not parse line: {}", message),
+            Err(message) => panic!("could not parse line: {:?}", message),
         };
         assert_eq!(card.get_id(), 1);
         assert_eq!(card.get_points(), 16);
@@ -631,10 +857,10 @@ mod tests {
     #[test]
     fn test_parse_line_2() {
         let input = "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14 22";
-        let (_, card) = parse_line(input).unwrap();
-        let card = match card {
+        let (_, card_builder) = parse_line(input).unwrap();
+        let card = match card_builder.build() {
             Ok(card) => card,
-            Err(message) => panic!("could not parse line: {}", message),
+            Err(message) => panic!("could not parse line: {:?}", message),
         };
         assert_eq!(card.get_id(), 3);
         assert_eq!(card.get_points(), 1);
@@ -643,10 +869,10 @@ mod tests {
     #[test]
     fn test_parse_line_3() {
         let input = "Card 3: 11 21 53 59 44 | 69 82 63 72 16 21 14  1";
-        let (_, card) = parse_line(input).unwrap();
-        let card = match card {
+        let (_, card_builder) = parse_line(input).unwrap();
+        let card = match card_builder.build() {
             Ok(card) => card,
-            Err(message) => panic!("could not parse line: {}", message),
+            Err(message) => panic!("could not parse line: {:?}", message),
         };
         assert_eq!(card.get_id(), 3);
         assert_eq!(card.get_points(), 1);
@@ -655,32 +881,153 @@ mod tests {
     #[test]
     fn test_parse_line_4() {
         let input = "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1";
-        let (_, card) = parse_line(input).unwrap();
-        let card = match card {
+        let (_, card_builder) = parse_line(input).unwrap();
+        let card = match card_builder.build() {
             Ok(card) => card,
-            Err(message) => panic!("could not parse line: {}", message),
+            Err(message) => panic!("could not parse line: {:?}", message),
         };
         assert_eq!(card.get_id(), 3);
         assert_eq!(card.get_points(), 2);
     }
 
     #[test]
-    fn test_part_1_with_test_input() {
-        assert_eq!(part_1(TEST_INPUT_01), 13);
+    fn test_card_shapes() {
+        assert_eq!(CardShape::puzzle().check(10, 25), Vec::<String>::new());
+        assert_eq!(CardShape::Unrestricted.check(0, 100), Vec::<String>::new());
+        assert_eq!(
+            CardShape::of(3, 4).check(2, 4),
+            vec!["there should be 3 winning numbers but there are 2"]
+        );
+        assert_eq!(
+            CardShape::Ranges {
+                winning: 1..=3,
+                received: 4..=6
+            }
+            .check(4, 3),
+            vec![
+                "there should be 1 to 3 winning numbers but there are 4",
+                "there should be 4 to 6 received numbers but there are 3"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_card_reader_infers_the_shape_of_the_first_card() {
+        let mut reader = CardReader::new();
+
+        assert!(reader.read(1, "Card 1: 1 2 3 | 4 5").is_ok());
+        assert!(reader.read(2, "Card 2: 6 7 8 | 9 10").is_ok());
+        assert_eq!(
+            reader.read(3, "Card 3: 1 2 | 3 4 5"),
+            Err(AocError::Invalid(
+                "line 3: there should be 3 winning numbers but there are 2, \
+                 there should be 2 received numbers but there are 3"
+                    .to_string()
+            ))
+        );
+
+        let mut reader = CardReader::with_shape(CardShape::Unrestricted);
+        assert!(reader.read(1, "Card 1: 1 2 3 | 4 5").is_ok());
+        assert!(reader.read(2, "Card 2: 1 | 2 3 4 5").is_ok());
+    }
+
+    #[test]
+    fn test_card_reader_warns_of_duplicates() {
+        let mut reader = CardReader::new();
+        let card = reader.read(7, "Card 7: 1 2 2 | 2 3 3").unwrap();
+
+        assert_eq!(card.get_total_matching_numbers(), 1);
+        assert_eq!(
+            reader.warnings(),
+            [
+                "line 7: winning number 2 is written 2 times on card 7",
+                "line 7: received number 3 is written 2 times on card 7"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicates_do_not_count_towards_the_shape() {
+        let mut reader = CardReader::new();
+        assert!(reader.read(1, "Card 1: 1 2 3 | 4 5").is_ok());
+        assert!(reader.read(2, "Card 2: 6 6 7 8 | 9 10 10").is_ok());
+
+        let mut reader = CardReader::with_shape(CardShape::puzzle());
+        assert!(reader
+            .read(1, "Card 1: 1 2 3 4 5 5 | 1 2 3 4 5 6 7 8")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_streamed_warnings() {
+        let input = "Card 1: 1 2 2 | 2 3\nCard 2: 4 5 | 6 7";
+        let warnings = vec!["line 1: winning number 2 is written 2 times on card 1"];
+
+        for part in [1, 2] {
+            let (_, streamed) = Day04::stream_part(&mut input.as_bytes(), part).unwrap();
+            assert_eq!(streamed, warnings);
+        }
+        assert_eq!(Day04::parse(input).unwrap().warnings(), warnings);
     }
 
     #[test]
-    fn test_part_1_with_complete_input() {
-        assert_eq!(part_1(COMPLETE_INPUT_01), 23028);
+    fn test_part_1_with_test_input() {
+        assert_eq!(part_1(TEST_INPUT_01.as_bytes()), Ok(13));
     }
 
     #[test]
     fn test_part_2_with_test_input() {
-        assert_eq!(part_2(TEST_INPUT_01), 30);
+        assert_eq!(part_2(TEST_INPUT_01.as_bytes()), Ok(30));
+    }
+
+    #[test]
+    fn test_part_2_refuses_copies_past_the_end() {
+        let input = "Card 1: 1 2 | 3 4\nCard 2: 5 6 | 5 6\nCard 3: 7 8 | 9 10";
+
+        assert_eq!(
+            part_2(input.as_bytes()),
+            Err(AocError::Invalid(
+                "card 2 wins copies of 1 cards past the last one".to_string()
+            ))
+        );
+        assert_eq!(
+            part_2("Card 1: 1 2 | 1 9\nCard 2: 3 4 | 5 6".as_bytes()),
+            Ok(3)
+        );
     }
 
     #[test]
-    fn test_part_2_with_complete_input() {
-        assert_eq!(part_2(COMPLETE_INPUT_01), 9236992);
+    fn test_streamed_overflow() {
+        let input = "Card 1: 1 2 | 3 4\nCard 2: 5 6 | 5 6\nCard 3: 7 8 | 7 8";
+        let expected = Err(AocError::Invalid(
+            "card 2 wins copies of 1 cards past the last one".to_string(),
+        ));
+
+        assert_eq!(part_2(input.as_bytes()), expected);
+        assert_eq!(
+            Day04::parse(input).and_then(|day| get_total_scratchcards(&day.cards)),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        assert_display_round_trip(TEST_INPUT_01);
+        assert_display_round_trip(INPUT_01);
+
+        assert_eq!(
+            CardReader::new()
+                .read(1, "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53")
+                .unwrap()
+                .to_string(),
+            "Card 1: 17 41 48 83 86 |  6  9 17 31 48 53 83 86"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_display_round_trip_of_generated_cards(seed in any::<u64>(), size in 0..20usize) {
+            assert_display_round_trip(&generate(4, seed, size).unwrap());
+        }
     }
 }
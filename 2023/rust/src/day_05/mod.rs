@@ -1,4 +1,10 @@
-use std::{cmp, collections::HashMap, ops::Range};
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    ops::Range,
+    str::FromStr,
+};
 
 use nom::{
     bytes::complete::{tag, take_until},
@@ -8,24 +14,47 @@ use nom::{
     IResult,
 };
 
-pub fn part_1(input: &str) -> Result<u64, String> {
-    let Ok((_, almanac)) = parse_almanac(input) else {
-        return Err("Failed to parse almanac".to_string());
-    };
+use aoc_intervals::IntervalSet;
+
+use crate::error::{parse_all, AocError};
+use crate::solution::{Answer, Solution};
+
+mod piecewise;
+
+pub use piecewise::PiecewiseMap;
+
+pub fn part_1(input: &str) -> Result<u64, AocError> {
+    let almanac = input.parse::<Almanac>()?;
+
+    almanac.get_closest_seed_location()
+}
+
+pub fn part_2(input: &str) -> Result<u64, AocError> {
+    let almanac = input.parse::<Almanac>()?;
 
-    almanac
-        .get_closest_seed_location()
-        .ok_or("Failed to find closest seed location".to_string())
+    almanac.get_closest_location_for_range_of_seeds()
 }
 
-pub fn part_2(input: &str) -> Result<u64, String> {
-    let Ok((_, almanac)) = parse_almanac(input) else {
-        return Err("Failed to parse almanac".to_string());
-    };
+pub struct Day05 {
+    almanac: Almanac,
+}
+
+impl Solution for Day05 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day05 {
+            almanac: input.parse()?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.almanac.get_closest_seed_location()?))
+    }
 
-    almanac
-        .get_closest_location_for_range_of_seeds()
-        .ok_or("Failed to find closest seed location".to_string())
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.almanac.get_closest_location_for_range_of_seeds()?,
+        ))
+    }
 }
 
 fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
@@ -87,157 +116,439 @@ fn parse_almanac_range(input: &str) -> IResult<&str, AlmanacRange> {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct Almanac {
+pub struct Almanac {
     seeds: Vec<u64>,
     maps: HashMap<String, AlmanacMap>,
 }
 
 impl Almanac {
-    fn get_closest_seed_location(&self) -> Option<u64> {
-        self.seeds
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    /// The maps that convert the category `from` into the category `to`, in order.
+    ///
+    /// The maps are the edges of a graph between the categories in their `X-to-Y` names, and
+    /// there must be a single way through it, so there can be no cycle on the way either. A
+    /// cycle is on the way when all of its categories are on a way from `from` to `to`.
+    pub fn chain(&self, from: &str, to: &str) -> Result<Vec<&AlmanacMap>, AocError> {
+        let mut maps_from = HashMap::<&str, Vec<&AlmanacMap>>::new();
+        for map in self.maps.values() {
+            let (source, _) = map.categories().ok_or(AocError::Invalid(format!(
+                "the '{}' map is not named after the categories it converts",
+                map.name
+            )))?;
+            maps_from.entry(source).or_default().push(map);
+        }
+        for maps in maps_from.values_mut() {
+            maps.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        let mut cycles = vec![];
+        let chains = find_chains(&maps_from, from, to, &mut vec![], &mut cycles);
+
+        let on_the_way = chains
             .iter()
-            .map(|seed| self.get_seed_location(seed))
-            .min()
+            .flatten()
+            .filter_map(|map| map.categories())
+            .flat_map(|(source, destination)| [source, destination])
+            .collect::<HashSet<_>>();
+        if let Some(cycle) = cycles.iter().find(|cycle| {
+            cycle
+                .iter()
+                .all(|category| on_the_way.contains(category.as_str()))
+        }) {
+            return Err(AocError::Invalid(format!(
+                "the maps go round in a cycle: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        match chains.as_slice() {
+            [] => Err(AocError::Invalid(format!(
+                "there are no maps from {} to {}",
+                from, to
+            ))),
+            [chain] => Ok(chain.clone()),
+            chains => Err(AocError::Invalid(format!(
+                "there are several ways from {} to {}: {}",
+                from,
+                to,
+                chains
+                    .iter()
+                    .map(|chain| describe_chain(from, chain))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    /// Converts a `from` number into a `to` number.
+    pub fn convert(&self, from: &str, to: &str, value: u64) -> Result<u64, AocError> {
+        let chain = self.chain(from, to)?;
+
+        Ok(chain.iter().fold(value, |value, map| map.map(&value)))
     }
 
-    fn get_seed_location(&self, seed: &u64) -> u64 {
-        let value = self.get_map("seed-to-soil").map(seed);
-        let value = self.get_map("soil-to-fertilizer").map(&value);
-        let value = self.get_map("fertilizer-to-water").map(&value);
-        let value = self.get_map("water-to-light").map(&value);
-        let value = self.get_map("light-to-temperature").map(&value);
-        let value = self.get_map("temperature-to-humidity").map(&value);
-        let value = self.get_map("humidity-to-location").map(&value);
+    /// Converts ranges of `from` numbers into the ranges of `to` numbers they become, sorted
+    /// and without overlaps.
+    pub fn convert_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: Vec<Range<u64>>,
+    ) -> Result<Vec<Range<u64>>, AocError> {
+        let chain = self.chain(from, to)?;
 
-        value
+        Ok(chain
+            .iter()
+            .fold(ranges, |ranges, map| map.map_ranges(ranges)))
     }
 
-    fn get_closest_location_for_range_of_seeds(&self) -> Option<u64> {
+    /// The ranges of `from` numbers that are converted into the `to` numbers in `ranges`,
+    /// sorted and without overlaps.
+    pub fn invert_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: Vec<Range<u64>>,
+    ) -> Result<Vec<Range<u64>>, AocError> {
+        let chain = self.chain(from, to)?;
+
+        Ok(chain
+            .iter()
+            .rev()
+            .fold(ranges, |ranges, map| map.inverse_map_ranges(ranges)))
+    }
+
+    /// A single map that converts `from` numbers into `to` numbers as the whole chain of
+    /// maps between them does.
+    pub fn collapse(&self, from: &str, to: &str) -> Result<AlmanacMap, AocError> {
+        let identity = AlmanacMap {
+            name: format!("{}-to-{}", from, from),
+            ranges: vec![],
+        };
+
+        self.chain(from, to)?
+            .into_iter()
+            .try_fold(identity, |collapsed, map| collapsed.compose(map))
+    }
+
+    fn get_closest_seed_location(&self) -> Result<u64, AocError> {
+        let locations = PiecewiseMap::from(&self.collapse("seed", "location")?);
+
+        self.seeds
+            .iter()
+            .map(|&seed| locations.map(seed))
+            .min()
+            .ok_or(AocError::NoSolution("there are no seeds".to_string()))
+    }
+
+    fn get_closest_location_for_range_of_seeds(&self) -> Result<u64, AocError> {
+        let locations = PiecewiseMap::from(&self.collapse("seed", "location")?);
+
         self.seeds
             .as_slice()
             .chunks(2)
-            .filter_map(|r| {
-                let start = r[0];
-                let length = r[1];
-                let end = start + length;
-
-                self.get_locations_for_range_of_seeds(start..end)
-            })
-            .flatten()
-            .map(|r| r.start)
+            .filter(|r| r.len() == 2)
+            .filter_map(|r| locations.min_over(r[0]..r[0] + r[1]))
             .min()
+            .ok_or(AocError::NoSolution("there are no seeds".to_string()))
     }
+}
+
+/// Every way of going from `from` to `to` through the maps that never comes back to a
+/// category, along with the cycles met on the way, which `visiting` keeps track of.
+fn find_chains<'a>(
+    maps_from: &HashMap<&str, Vec<&'a AlmanacMap>>,
+    from: &str,
+    to: &str,
+    visiting: &mut Vec<String>,
+    cycles: &mut Vec<Vec<String>>,
+) -> Vec<Vec<&'a AlmanacMap>> {
+    if from == to {
+        return vec![vec![]];
+    }
+
+    if let Some(i) = visiting.iter().position(|category| category == from) {
+        let mut cycle = visiting[i..].to_vec();
+        cycle.push(from.to_string());
+        cycles.push(cycle);
+
+        return vec![];
+    }
+
+    visiting.push(from.to_string());
+    let mut chains = vec![];
+    for &map in maps_from.get(from).into_iter().flatten() {
+        let (_, destination) = map.categories().unwrap_or_default();
+        for chain in find_chains(maps_from, destination, to, visiting, cycles) {
+            chains.push([map].into_iter().chain(chain).collect());
+        }
+    }
+    visiting.pop();
+
+    chains
+}
+
+fn describe_chain(from: &str, chain: &[&AlmanacMap]) -> String {
+    let categories = chain
+        .iter()
+        .filter_map(|map| map.categories())
+        .map(|(_, destination)| destination);
+
+    [from]
+        .into_iter()
+        .chain(categories)
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
 
-    fn get_locations_for_range_of_seeds(&self, range: Range<u64>) -> Option<Vec<Range<u64>>> {
-        let value = self.get_map("seed-to-soil").map_ranges(vec![range]);
-        let value = self.get_map("soil-to-fertilizer").map_ranges(value);
-        let value = self.get_map("fertilizer-to-water").map_ranges(value);
-        let value = self.get_map("water-to-light").map_ranges(value);
-        let value = self.get_map("light-to-temperature").map_ranges(value);
-        let value = self.get_map("temperature-to-humidity").map_ranges(value);
-        let value = self.get_map("humidity-to-location").map_ranges(value);
+impl FromStr for Almanac {
+    type Err = AocError;
 
-        Some(value)
+    /// Parses an almanac whose ranges all fit in a `u64`, so no conversion can overflow.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let almanac = parse_all(s, parse_almanac)?;
+        for range in almanac.maps.values().flat_map(|map| &map.ranges) {
+            range.check()?;
+        }
+
+        Ok(almanac)
     }
+}
+
+/// Writes the almanac as the puzzle input, following the maps from seed to location and
+/// then writing any map off that chain by name.
+impl Display for Almanac {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seeds = self.seeds.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        writeln!(f, "seeds: {}", seeds.join(" "))?;
+
+        let mut maps = vec![];
+        let mut category = "seed";
+        while let Some(map) = self
+            .maps
+            .values()
+            .find(|map| map.name.starts_with(&format!("{}-to-", category)) && !maps.contains(map))
+        {
+            maps.push(map);
+            category = &map.name[category.len() + "-to-".len()..];
+        }
+
+        let mut rest = self
+            .maps
+            .values()
+            .filter(|map| !maps.contains(map))
+            .collect::<Vec<_>>();
+        rest.sort_by(|a, b| a.name.cmp(&b.name));
+        maps.extend(rest);
+
+        for map in maps {
+            write!(f, "\n{}", map)?;
+        }
 
-    fn get_map(&self, map_name: &str) -> &AlmanacMap {
-        self.maps
-            .get(map_name)
-            .expect(format!("could not find '{}' map", map_name).as_str())
+        Ok(())
     }
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct AlmanacMap {
+pub struct AlmanacMap {
     name: String,
     ranges: Vec<AlmanacRange>,
 }
 
 impl AlmanacMap {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The categories the map converts from and to, read from its `X-to-Y` name.
+    pub fn categories(&self) -> Option<(&str, &str)> {
+        self.name.split_once("-to-")
+    }
+
     fn map(&self, seed: &u64) -> u64 {
         for range in &self.ranges {
             if range.contains(seed) {
                 return range.map(seed);
             }
         }
-        return *seed;
+        *seed
     }
 
-    fn map_ranges(&self, ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
-        ranges
-            .into_iter()
-            .map(|r| self.map_range(r))
-            .flatten()
-            .collect()
+    /// Every number that the map converts into `value`: those of the ranges whose
+    /// destinations hold it, and `value` itself unless a range converts it into something
+    /// else. A number that an earlier range converts is left out of the later ones.
+    pub fn inverse_map(&self, value: &u64) -> Vec<u64> {
+        let mut sources = self
+            .ranges
+            .iter()
+            .filter_map(|range| range.inverse_map(value))
+            .chain([*value])
+            .filter(|source| self.map(source) == *value)
+            .collect::<Vec<_>>();
+        sources.sort_unstable();
+        sources.dedup();
+
+        sources
     }
 
-    fn map_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
-        let mut result = Vec::new();
-        let mut current_seed = range.start;
+    /// The numbers that the map converts into the numbers of `ranges`, sorted and without
+    /// overlaps.
+    pub fn inverse_map_ranges(&self, ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        let targets = ranges.into_iter().collect::<IntervalSet<_>>();
+        let mut sources = IntervalSet::new();
+        let mut converted = IntervalSet::new();
 
-        while range.contains(&current_seed) {
-            let range_mapper = match self.get_range_mapper(&current_seed) {
-                Some(range) => range,
-                None => self.create_range_mapper(current_seed..range.end),
-            };
-            let range = range_mapper.map_range(current_seed..range.end);
-            current_seed = current_seed + (range.end - range.start);
-            result.push(range);
+        for range in &self.ranges {
+            // an earlier range converts the numbers it shares with this one
+            let own = IntervalSet::from(range.source()).difference(&converted);
+            let shifted = targets.shift(range.destination(), range.source_start);
+            sources = sources.union(&shifted.intersection(&own));
+            converted = converted.union(&IntervalSet::from(range.source()));
         }
 
-        result
+        sources.union(&targets.difference(&converted)).into_ranges()
     }
 
-    fn get_range_mapper(&self, seed: &u64) -> Option<AlmanacRange> {
-        for range in self.ranges.as_slice() {
-            if range.contains(seed) {
-                return Some(range.clone());
-            }
+    /// The numbers the map converts the numbers of `ranges` into, sorted and without
+    /// overlaps.
+    fn map_ranges(&self, ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        let mut unconverted = ranges.into_iter().collect::<IntervalSet<_>>();
+        let mut converted = IntervalSet::new();
+
+        for range in &self.ranges {
+            let shifted = unconverted.shift(range.source(), range.destination_starts);
+            converted = converted.union(&shifted);
+            unconverted = unconverted.difference(&IntervalSet::from(range.source()));
         }
 
-        None
+        converted.union(&unconverted).into_ranges()
     }
 
-    fn create_range_mapper(&self, range: Range<u64>) -> AlmanacRange {
-        let start = range.start;
+    /// The ranges that convert the numbers of `range`, cut to it and in order, where the
+    /// numbers that no range converts are in ranges that convert them into themselves.
+    fn split(&self, range: Range<u64>) -> Vec<AlmanacRange> {
+        let mut unconverted = IntervalSet::from(range);
+        let mut pieces = vec![];
 
-        let end = self
-            .ranges
-            .iter()
-            .filter_map(|r| {
-                if r.source_start > start {
-                    return Some(r.source_start);
+        for range in &self.ranges {
+            let sources = IntervalSet::from(range.source());
+            pieces.extend(
+                unconverted
+                    .intersection(&sources)
+                    .ranges()
+                    .iter()
+                    .map(|part| AlmanacRange {
+                        destination_starts: range.map(&part.start),
+                        source_start: part.start,
+                        length: part.end - part.start,
+                    }),
+            );
+            unconverted = unconverted.difference(&sources);
+        }
+        pieces.extend(unconverted.ranges().iter().map(|part| AlmanacRange {
+            destination_starts: part.start,
+            source_start: part.start,
+            length: part.end - part.start,
+        }));
+        pieces.sort_by_key(|piece| piece.source_start);
+
+        pieces
+    }
+
+    /// The map that converts numbers as this map and then `other` do.
+    ///
+    /// Its ranges are sorted and apart, those next to each other that shift numbers as much
+    /// are joined, and there are none that convert numbers into themselves, so a whole
+    /// chain of maps collapses into a single one no longer than it needs to be.
+    ///
+    /// Fails if a range of either map goes past the largest `u64`.
+    pub fn compose(&self, other: &AlmanacMap) -> Result<AlmanacMap, AocError> {
+        for range in self.ranges.iter().chain(&other.ranges) {
+            range.check()?;
+        }
+
+        let name = match (self.categories(), other.categories()) {
+            (Some((source, _)), Some((_, destination))) => {
+                format!("{}-to-{}", source, destination)
+            }
+            _ => format!("{}-then-{}", self.name, other.name),
+        };
+
+        let mut ranges: Vec<AlmanacRange> = vec![];
+        for piece in self.split(0..u64::MAX) {
+            for next in other.split(piece.destination()) {
+                let range = AlmanacRange {
+                    destination_starts: next.destination_starts,
+                    source_start: piece.source_start
+                        + (next.source_start - piece.destination_starts),
+                    length: next.length,
+                };
+                if range.destination_starts == range.source_start {
+                    continue;
                 }
-                None
-            })
-            .min_by_key(|x| x - start);
-
-        if end.is_none() {
-            return AlmanacRange {
-                destination_starts: start,
-                source_start: start,
-                length: range.end - start,
-            };
+
+                match ranges.last_mut() {
+                    Some(last)
+                        if last.source().end == range.source_start
+                            && last.destination().end == range.destination_starts =>
+                    {
+                        last.length += range.length
+                    }
+                    _ => ranges.push(range),
+                }
+            }
         }
 
-        let end = end.unwrap();
+        Ok(AlmanacMap { name, ranges })
+    }
+}
 
-        AlmanacRange {
-            destination_starts: start,
-            source_start: start,
-            length: end - start,
+impl Display for AlmanacMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} map:", self.name)?;
+        for range in &self.ranges {
+            writeln!(f, "{}", range)?;
         }
+
+        Ok(())
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-struct AlmanacRange {
+pub struct AlmanacRange {
     destination_starts: u64,
     source_start: u64,
     length: u64,
 }
 
 impl AlmanacRange {
+    /// Fails unless the numbers the range converts and those it converts them into all fit
+    /// in a `u64`, which every other method relies on.
+    fn check(&self) -> Result<(), AocError> {
+        for start in [self.source_start, self.destination_starts] {
+            start
+                .checked_add(self.length)
+                .ok_or(AocError::Invalid(format!(
+                    "the range '{}' goes past {}",
+                    self,
+                    u64::MAX
+                )))?;
+        }
+
+        Ok(())
+    }
+
+    fn source(&self) -> Range<u64> {
+        self.source_start..self.source_start + self.length
+    }
+
+    fn destination(&self) -> Range<u64> {
+        self.destination_starts..self.destination_starts + self.length
+    }
+
     fn contains(&self, seed: &u64) -> bool {
         (self.source_start..self.source_start + self.length).contains(seed)
     }
@@ -246,17 +557,43 @@ impl AlmanacRange {
         self.destination_starts + (seed - self.source_start)
     }
 
-    fn map_range(&self, range: Range<u64>) -> Range<u64> {
-        self.map(&range.start)..self.map(cmp::min(&(self.source_start + self.length), &range.end))
+    /// The number converted into `value`, if the destination of the range holds it.
+    pub fn inverse_map(&self, value: &u64) -> Option<u64> {
+        (self.destination_starts..self.destination_starts + self.length)
+            .contains(value)
+            .then(|| self.source_start + (value - self.destination_starts))
+    }
+
+    /// The numbers converted into the part of `range` that the destination holds.
+    pub fn inverse_map_range(&self, range: Range<u64>) -> Option<Range<u64>> {
+        let start = cmp::max(range.start, self.destination_starts);
+        let end = cmp::min(range.end, self.destination_starts + self.length);
+
+        (start < end).then(|| {
+            self.source_start + (start - self.destination_starts)
+                ..self.source_start + (end - self.destination_starts)
+        })
+    }
+}
+
+impl Display for AlmanacRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.destination_starts, self.source_start, self.length
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::generate::generate;
 
     const TEST_INPUT: &str = include_str!("../day_05/test_input.txt");
-    const COMPLETE_INPUT: &str = include_str!("../day_05/input.txt");
 
     #[test]
     fn test_parse_seeds() {
@@ -437,17 +774,462 @@ soil-to-fertilizer map:
     }
 
     #[test]
-    fn test_part_1_with_complete_input() {
-        assert_eq!(part_1(COMPLETE_INPUT), Ok(462648396));
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT), Ok(46));
+    }
+
+    const INPUT: &str = include_str!("../day_05/input.txt");
+
+    fn map_names(chain: Vec<&AlmanacMap>) -> Vec<&str> {
+        chain.into_iter().map(|map| map.name()).collect()
+    }
+
+    /// An almanac with an empty map for every name.
+    fn almanac_of_maps(names: &[&str]) -> Almanac {
+        let maps = names
+            .iter()
+            .map(|name| format!("{} map:\n0 0 1", name))
+            .collect::<Vec<_>>();
+
+        format!("seeds: 1\n\n{}\n", maps.join("\n\n"))
+            .parse()
+            .unwrap()
     }
 
     #[test]
-    fn test_part_2_with_test_input() {
-        assert_eq!(part_2(TEST_INPUT), Ok(46));
+    fn test_chain() {
+        let almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+
+        assert_eq!(
+            almanac.chain("soil", "light").map(map_names),
+            Ok(vec![
+                "soil-to-fertilizer",
+                "fertilizer-to-water",
+                "water-to-light"
+            ])
+        );
+        assert_eq!(almanac.chain("seed", "location").unwrap().len(), 7);
+        assert_eq!(almanac.chain("water", "water").map(map_names), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_chain_past_a_cycle() {
+        let almanac = almanac_of_maps(&["a-to-b", "b-to-a", "a-to-c"]);
+
+        assert_eq!(almanac.chain("a", "c").map(map_names), Ok(vec!["a-to-c"]));
+        assert_eq!(
+            almanac.chain("b", "c"),
+            Err(AocError::Invalid(
+                "the maps go round in a cycle: b -> a -> b".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_convert() {
+        let almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+
+        for seed in [79, 14, 55, 13] {
+            let soil = almanac.convert("seed", "soil", seed).unwrap();
+            let light = almanac.convert("soil", "light", soil).unwrap();
+
+            assert_eq!(
+                almanac.convert("light", "location", light),
+                almanac.convert("seed", "location", seed)
+            );
+        }
+        assert_eq!(almanac.convert("seed", "location", 79), Ok(82));
+        // the ranges come out sorted and merged, whatever order they go in
+        assert_eq!(
+            almanac.convert_ranges("seed", "soil", vec![79..93, 55..68]),
+            Ok(vec![57..70, 81..95])
+        );
+        assert_eq!(
+            almanac.convert_ranges("seed", "soil", vec![79..93, 55..68, 60..80]),
+            Ok(vec![57..95])
+        );
     }
 
     #[test]
-    fn test_part_2_with_complete_input() {
-        assert_eq!(part_2(COMPLETE_INPUT), Ok(2520479));
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_inverse_map() {
+        let range = AlmanacRange {
+            destination_starts: 52,
+            source_start: 50,
+            length: 48,
+        };
+        assert_eq!(range.inverse_map(&81), Some(79));
+        assert_eq!(range.inverse_map(&50), None);
+        assert_eq!(range.inverse_map_range(40..60), Some(50..58));
+        assert_eq!(range.inverse_map_range(100..110), None);
+
+        let almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+        let map = &almanac.maps["seed-to-soil"];
+        assert_eq!(map.inverse_map(&81), vec![79]);
+        assert_eq!(map.inverse_map(&50), vec![98]);
+        assert_eq!(map.inverse_map(&52), vec![50]);
+        assert_eq!(map.inverse_map(&10), vec![10]);
+        assert_eq!(map.inverse_map(&99), vec![97]);
+        assert_eq!(map.inverse_map_ranges(vec![48..53]), vec![48..51, 98..100]);
+
+        // 5..10 is converted by the first range, so the second one only converts 10..15
+        let map = AlmanacMap {
+            name: "a-to-b".to_string(),
+            ranges: vec![
+                AlmanacRange {
+                    destination_starts: 100,
+                    source_start: 5,
+                    length: 5,
+                },
+                AlmanacRange {
+                    destination_starts: 200,
+                    source_start: 5,
+                    length: 10,
+                },
+            ],
+        };
+        assert_eq!(map.inverse_map(&202), vec![202]);
+        assert_eq!(map.inverse_map(&207), vec![12, 207]);
+        assert_eq!(map.inverse_map(&102), vec![7, 102]);
+        assert_eq!(map.inverse_map(&7), vec![]);
+        assert_eq!(
+            map.inverse_map_ranges(vec![200..215]),
+            vec![10..15, 200..215]
+        );
+        assert_eq!(map.inverse_map_ranges(vec![0..20]), vec![0..5, 15..20]);
+        for value in 0..220 {
+            for source in map.inverse_map(&value) {
+                assert_eq!(map.map(&source), value);
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_invert_ranges() {
+        let almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+
+        let seeds = almanac
+            .invert_ranges("seed", "location", vec![82..83])
+            .unwrap();
+        assert!(seeds.iter().any(|r| r.contains(&79)));
+        for seed in seeds.iter().flat_map(|r| r.clone()) {
+            assert_eq!(almanac.convert("seed", "location", seed), Ok(82));
+        }
+
+        for input in [TEST_INPUT, INPUT] {
+            let almanac = input.parse::<Almanac>().unwrap();
+
+            assert_eq!(
+                closest_location_by_inversion(&almanac),
+                almanac.get_closest_location_for_range_of_seeds().ok()
+            );
+        }
+
+        // a seed without a length starts no range
+        let mut almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+        almanac.seeds.push(1);
+        assert_eq!(closest_location_by_inversion(&almanac), Some(46));
+    }
+
+    #[test]
+    fn test_inversion_of_an_empty_seed_range() {
+        let mut almanac = almanac_of_maps(&MAP_NAMES);
+        almanac.seeds = vec![1, 0];
+
+        assert_eq!(closest_location_by_inversion(&almanac), None);
+        assert!(matches!(
+            almanac.get_closest_location_for_range_of_seeds(),
+            Err(AocError::NoSolution(_))
+        ));
+    }
+
+    /// The closest location of part 2, found as the smallest `n` such that the seeds of
+    /// locations `0..n` include one of the almanac.
+    #[allow(clippy::single_range_in_vec_init)]
+    fn closest_location_by_inversion(almanac: &Almanac) -> Option<u64> {
+        let seeds = almanac
+            .seeds
+            .chunks(2)
+            .filter(|r| r.len() == 2)
+            .map(|r| r[0]..r[0] + r[1])
+            .filter(|r| !r.is_empty())
+            .collect::<Vec<_>>();
+        let reaches_a_seed = |n: u64| {
+            almanac
+                .invert_ranges("seed", "location", vec![0..n])
+                .unwrap()
+                .iter()
+                .any(|r| seeds.iter().any(|s| r.start < s.end && s.start < r.end))
+        };
+
+        let (mut low, mut high) = (0, u64::MAX / 2);
+        if !reaches_a_seed(high) {
+            return None;
+        }
+        while high - low > 1 {
+            let middle = low + (high - low) / 2;
+            match reaches_a_seed(middle) {
+                true => high = middle,
+                false => low = middle,
+            }
+        }
+
+        Some(high - 1)
+    }
+
+    #[test]
+    fn test_compose() {
+        let almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+        let seed_to_soil = &almanac.maps["seed-to-soil"];
+        let soil_to_fertilizer = &almanac.maps["soil-to-fertilizer"];
+        let composed = seed_to_soil.compose(soil_to_fertilizer).unwrap();
+
+        assert_eq!(composed.name(), "seed-to-fertilizer");
+        for seed in 0..200 {
+            assert_eq!(
+                composed.map(&seed),
+                soil_to_fertilizer.map(&seed_to_soil.map(&seed))
+            );
+        }
+        assert!(composed
+            .ranges
+            .windows(2)
+            .all(|pair| { pair[0].source_start + pair[0].length <= pair[1].source_start }));
+        assert!(composed
+            .ranges
+            .iter()
+            .all(|range| range.source_start != range.destination_starts));
+    }
+
+    #[test]
+    fn test_ranges_up_to_the_largest_number() {
+        let last = u64::MAX - 10;
+        let almanac = format!(
+            "seeds: {} 10\n\nseed-to-soil map:\n0 {} 10\n\nsoil-to-location map:\n{} 0 5",
+            last, last, last
+        )
+        .parse::<Almanac>()
+        .unwrap();
+        let collapsed = almanac.collapse("seed", "location").unwrap();
+        let locations = PiecewiseMap::from(&collapsed);
+
+        assert_eq!(collapsed.map(&(u64::MAX - 1)), 9);
+        assert_eq!(locations.map(u64::MAX - 1), 9);
+        assert_eq!(locations.map(u64::MAX), u64::MAX);
+        assert_eq!(locations.map(last), last);
+        assert_eq!(almanac.get_closest_location_for_range_of_seeds(), Ok(5));
+
+        for range in ["0 1 18446744073709551615", "18446744073709551610 0 6"] {
+            assert_eq!(
+                format!("seeds: 1\n\nseed-to-soil map:\n{}", range).parse::<Almanac>(),
+                Err(AocError::Invalid(format!(
+                    "the range '{}' goes past {}",
+                    range,
+                    u64::MAX
+                )))
+            );
+        }
+
+        let overflowing = AlmanacMap {
+            name: "soil-to-location".to_string(),
+            ranges: vec![AlmanacRange {
+                destination_starts: u64::MAX,
+                source_start: 0,
+                length: 2,
+            }],
+        };
+        assert!(matches!(
+            collapsed.compose(&overflowing),
+            Err(AocError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_collapse() {
+        let almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+        let collapsed = almanac.collapse("seed", "location").unwrap();
+
+        assert_eq!(collapsed.name(), "seed-to-location");
+        assert_eq!(collapsed.map(&79), 82);
+        assert_eq!(
+            almanac.collapse("soil", "soil"),
+            Ok(AlmanacMap {
+                name: "soil-to-soil".to_string(),
+                ranges: vec![]
+            })
+        );
+        assert_eq!(
+            PiecewiseMap::from(&collapsed).len(),
+            collapsed.split(0..u64::MAX).len()
+        );
+    }
+
+    #[test]
+    fn test_chain_errors() {
+        let almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+        assert_eq!(
+            almanac.chain("location", "seed"),
+            Err(AocError::Invalid(
+                "there are no maps from location to seed".to_string()
+            ))
+        );
+
+        let almanac = almanac_of_maps(&["a-to-b", "b-to-c", "c-to-a", "c-to-d"]);
+        assert_eq!(
+            almanac.chain("a", "d"),
+            Err(AocError::Invalid(
+                "the maps go round in a cycle: a -> b -> c -> a".to_string()
+            ))
+        );
+        assert_eq!(
+            almanac.chain("a", "c").map(map_names),
+            Ok(vec!["a-to-b", "b-to-c"])
+        );
+
+        let almanac = almanac_of_maps(&["a-to-b", "b-to-c", "a-to-c"]);
+        assert_eq!(
+            almanac.chain("a", "c"),
+            Err(AocError::Invalid(
+                "there are several ways from a to c: a -> b -> c, a -> c".to_string()
+            ))
+        );
+
+        let almanac = almanac_of_maps(&["a-to-b", "b-to-c", "c-to-a"]);
+        assert_eq!(
+            almanac.chain("a", "d"),
+            Err(AocError::Invalid(
+                "there are no maps from a to d".to_string()
+            ))
+        );
+
+        let almanac = almanac_of_maps(&["a-to-b", "nonsense"]);
+        assert!(matches!(almanac.chain("a", "b"), Err(AocError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for input in [TEST_INPUT, INPUT] {
+            let almanac = parse_all(input, parse_almanac).unwrap();
+
+            assert_eq!(almanac.to_string().trim_end(), input.trim_end());
+            assert_eq!(parse_all(&almanac.to_string(), parse_almanac), Ok(almanac));
+        }
+    }
+
+    const MAP_NAMES: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+
+    /// The ranges of a map, which never overlap in their sources, as in the puzzle inputs.
+    fn almanac_ranges() -> impl Strategy<Value = Vec<AlmanacRange>> {
+        prop::collection::vec((0..10u64, 1..20u64, 0..200u64), 0..4).prop_map(|ranges| {
+            let mut source_start = 0;
+
+            ranges
+                .into_iter()
+                .map(|(gap, length, destination_starts)| {
+                    source_start += gap;
+                    let range = AlmanacRange {
+                        destination_starts,
+                        source_start,
+                        length,
+                    };
+                    source_start += length;
+
+                    range
+                })
+                .collect()
+        })
+    }
+
+    fn almanac() -> impl Strategy<Value = Almanac> {
+        (
+            prop::collection::vec((0..150u64, 0..30u64), 1..4),
+            prop::collection::vec(almanac_ranges(), MAP_NAMES.len()),
+        )
+            .prop_map(|(seeds, maps)| Almanac {
+                seeds: seeds
+                    .into_iter()
+                    .flat_map(|(start, length)| [start, length])
+                    .collect(),
+                maps: MAP_NAMES
+                    .iter()
+                    .zip(maps)
+                    .map(|(name, ranges)| {
+                        let map = AlmanacMap {
+                            name: name.to_string(),
+                            ranges,
+                        };
+
+                        (map.name.clone(), map)
+                    })
+                    .collect(),
+            })
+    }
+
+    proptest! {
+        /// Splitting the seed ranges along the maps finds the same closest location as
+        /// mapping every seed in them one by one.
+        #[test]
+        fn test_range_splitting_matches_every_seed(almanac in almanac()) {
+            let closest = almanac
+                .seeds
+                .chunks(2)
+                .flat_map(|r| r[0]..r[0] + r[1])
+                .map(|seed| almanac.convert("seed", "location", seed).unwrap())
+                .min();
+
+            prop_assert_eq!(almanac.get_closest_location_for_range_of_seeds().ok(), closest);
+        }
+
+        /// The collapsed chain converts every seed, and finds the closest location of every
+        /// range of seeds, as the maps one after the other do.
+        #[test]
+        fn test_collapsed_chain_matches_the_maps(almanac in almanac(), start in 0..300u64, length in 0..100u64) {
+            let collapsed = almanac.collapse("seed", "location").unwrap();
+            let locations = PiecewiseMap::from(&collapsed);
+            let convert = |seed| almanac.convert("seed", "location", seed).unwrap();
+
+            for seed in start..start + length {
+                prop_assert_eq!(collapsed.map(&seed), convert(seed));
+                prop_assert_eq!(locations.map(seed), convert(seed));
+            }
+            prop_assert_eq!(
+                locations.min_over(start..start + length),
+                (start..start + length).map(convert).min()
+            );
+        }
+
+        /// Inverting finds the closest location of part 2 as well as mapping forward does,
+        /// and the ranges of every seed are among the seeds of the ranges they become.
+        #[test]
+        fn test_inversion_matches_mapping(almanac in almanac()) {
+            prop_assert_eq!(
+                closest_location_by_inversion(&almanac),
+                almanac.get_closest_location_for_range_of_seeds().ok()
+            );
+
+            for range in almanac.seeds.chunks(2).map(|r| r[0]..r[0] + r[1]) {
+                let locations = almanac.convert_ranges("seed", "location", vec![range.clone()]).unwrap();
+                let seeds = almanac.invert_ranges("seed", "location", locations).unwrap();
+
+                prop_assert!(range.is_empty() || seeds.iter().any(|s| s.start <= range.start && range.end <= s.end));
+            }
+        }
+
+        #[test]
+        fn test_display_round_trip_of_generated_almanacs(seed in any::<u64>(), size in 0..20usize) {
+            let almanac = parse_all(&generate(5, seed, size).unwrap(), parse_almanac).unwrap();
+
+            prop_assert_eq!(parse_all(&almanac.to_string(), parse_almanac), Ok(almanac));
+        }
     }
 }
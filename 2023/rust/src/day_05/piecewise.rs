@@ -0,0 +1,137 @@
+use std::ops::Range;
+
+use super::AlmanacMap;
+
+/// A map laid out for queries: every number from 0 up belongs to one piece, which shifts
+/// all of its numbers by as much, so a number is converted by finding its piece with a
+/// binary search.
+///
+/// Within a piece the numbers keep their order, so the smallest number a range of numbers
+/// is converted into is either what its first number is converted into or what the first
+/// number of a later piece it covers is. The latter are found in a table of the smallest
+/// of every run of a power of two pieces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    /// Where every piece starts; the first one starts at 0, and every one ends where the
+    /// next one starts.
+    starts: Vec<u64>,
+    /// What the first number of every piece is converted into.
+    destinations: Vec<u64>,
+    /// `minimums[k][i]` is the smallest of `destinations[i..i + 2^k]`.
+    minimums: Vec<Vec<u64>>,
+}
+
+impl PiecewiseMap {
+    pub fn map(&self, value: u64) -> u64 {
+        let piece = self.piece(value);
+
+        self.destinations[piece] + (value - self.starts[piece])
+    }
+
+    /// The smallest number that the numbers of `range` are converted into.
+    pub fn min_over(&self, range: Range<u64>) -> Option<u64> {
+        if range.is_empty() {
+            return None;
+        }
+
+        let first = self.piece(range.start);
+        let last = self.piece(range.end - 1);
+        let start = self.map(range.start);
+
+        match first == last {
+            true => Some(start),
+            false => Some(start.min(self.min_of_pieces(first + 1..last + 1))),
+        }
+    }
+
+    /// The number of pieces the map is made of.
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    fn piece(&self, value: u64) -> usize {
+        self.starts.partition_point(|&start| start <= value) - 1
+    }
+
+    /// The smallest of the destinations of `pieces`, which cannot be empty.
+    fn min_of_pieces(&self, pieces: Range<usize>) -> u64 {
+        let k = pieces.len().ilog2() as usize;
+
+        self.minimums[k][pieces.start].min(self.minimums[k][pieces.end - (1 << k)])
+    }
+}
+
+impl From<&AlmanacMap> for PiecewiseMap {
+    fn from(map: &AlmanacMap) -> Self {
+        let (mut starts, mut destinations): (Vec<_>, Vec<_>) = map
+            .split(0..u64::MAX)
+            .into_iter()
+            .map(|piece| (piece.source_start, piece.destination_starts))
+            .unzip();
+
+        // no range holds the largest number, so it is converted into itself even when the
+        // last piece shifts the numbers before it
+        if starts.last() != destinations.last() {
+            starts.push(u64::MAX);
+            destinations.push(u64::MAX);
+        }
+
+        let mut minimums = vec![destinations.clone()];
+        while 1 << minimums.len() <= destinations.len() {
+            let half = 1 << (minimums.len() - 1);
+            let previous = minimums.last().unwrap();
+            let next = (0..previous.len() - half)
+                .map(|i| previous[i].min(previous[i + half]))
+                .collect();
+            minimums.push(next);
+        }
+
+        PiecewiseMap {
+            starts,
+            destinations,
+            minimums,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Almanac;
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../day_05/test_input.txt");
+
+    #[test]
+    fn test_map() {
+        let almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+        let locations = PiecewiseMap::from(&almanac.collapse("seed", "location").unwrap());
+
+        for seed in 0..200 {
+            assert_eq!(
+                Ok(locations.map(seed)),
+                almanac.convert("seed", "location", seed)
+            );
+        }
+        assert_eq!(locations.map(u64::MAX - 1), u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_min_over() {
+        let almanac = TEST_INPUT.parse::<Almanac>().unwrap();
+        let locations = PiecewiseMap::from(&almanac.collapse("seed", "location").unwrap());
+
+        for start in 0..120 {
+            for end in start..start + 40 {
+                assert_eq!(
+                    locations.min_over(start..end),
+                    (start..end).map(|seed| locations.map(seed)).min()
+                );
+            }
+        }
+        assert_eq!(locations.min_over(79..93), Some(46));
+    }
+}
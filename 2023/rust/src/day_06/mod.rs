@@ -3,11 +3,15 @@ use nom::{
     bytes::complete::tag,
     character::complete::{digit1, line_ending, multispace1, newline, u64},
     combinator::eof,
+    error::{Error, ErrorKind},
     multi::separated_list1,
     sequence::{preceded, terminated},
     IResult,
 };
 
+use crate::error::{parse_all, AocError};
+use crate::solution::{Answer, Solution};
+
 #[derive(Debug, Eq, PartialEq)]
 struct Race {
     time: u64,
@@ -30,10 +34,8 @@ impl Race {
     }
 }
 
-pub fn part_1(input: &str) -> Result<u64, String> {
-    let Ok((_, races)) = parse_input(input) else {
-        return Err("Failed to parse input".to_string());
-    };
+pub fn part_1(input: &str) -> Result<u64, AocError> {
+    let races = parse_all(input, parse_input)?;
 
     Ok(races
         .into_iter()
@@ -48,7 +50,7 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Race>> {
 
     let races = time
         .into_iter()
-        .zip(distance.into_iter())
+        .zip(distance)
         .map(|(t, d)| Race {
             time: t,
             record_distance: d,
@@ -76,14 +78,39 @@ fn parse_numbers(input: &str) -> IResult<&str, Vec<u64>> {
     preceded(multispace1, separated_list1(multispace1, u64))(input)
 }
 
-pub fn part_2(input: &str) -> Result<u64, String> {
-    let Ok((_, race)) = parse_input_as_one_number(input) else {
-        return Err("Failed to parse input".to_string());
-    };
+pub fn part_2(input: &str) -> Result<u64, AocError> {
+    let race = parse_all(input, parse_input_as_one_number)?;
 
     Ok(race.count_possible_ways_to_win())
 }
 
+pub struct Day06 {
+    races: Vec<Race>,
+    race: Race,
+}
+
+impl Solution for Day06 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let races = parse_all(input, parse_input)?;
+        let race = parse_all(input, parse_input_as_one_number)?;
+
+        Ok(Day06 { races, race })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.races
+                .iter()
+                .map(|r| r.count_possible_ways_to_win())
+                .product::<u64>(),
+        ))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.race.count_possible_ways_to_win()))
+    }
+}
+
 fn parse_input_as_one_number(input: &str) -> IResult<&str, Race> {
     let (input, time) = parse_time_as_one_number(input)?;
     let (input, distance) = parse_distance_as_one_number(input)?;
@@ -98,23 +125,27 @@ fn parse_input_as_one_number(input: &str) -> IResult<&str, Race> {
 }
 fn parse_time_as_one_number(input: &str) -> IResult<&str, u64> {
     let (input, _) = tag("Time:")(input)?;
-    let (input, chars) = parse_as_one_number(input)?;
 
-    Ok((input, chars.concat().parse().unwrap()))
+    parse_as_one_number(input)
 }
 
 fn parse_distance_as_one_number(input: &str) -> IResult<&str, u64> {
     let (input, _) = tag("Distance:")(input)?;
-    let (input, chars) = parse_as_one_number(input)?;
 
-    Ok((input, chars.concat().parse().unwrap()))
+    parse_as_one_number(input)
 }
 
-fn parse_as_one_number(input: &str) -> IResult<&str, Vec<&str>> {
-    terminated(
+fn parse_as_one_number(input: &str) -> IResult<&str, u64> {
+    let (remaining, chars) = terminated(
         preceded(multispace1, separated_list1(multispace1, digit1)),
         alt((line_ending, eof)),
-    )(input)
+    )(input)?;
+
+    let Ok(number) = chars.concat().parse() else {
+        return Err(nom::Err::Failure(Error::new(input, ErrorKind::TooLarge)));
+    };
+
+    Ok((remaining, number))
 }
 
 #[cfg(test)]
@@ -122,7 +153,6 @@ mod tests {
     use super::*;
 
     const TEST_INPUT: &str = include_str!("../day_06/test_input.txt");
-    const COMPLETE_INPUT: &str = include_str!("../day_06/complete_input.txt");
 
     #[test]
     fn test_parse_time() {
@@ -188,11 +218,6 @@ Distance:  9  40  200"#;
         assert_eq!(part_1(TEST_INPUT), Ok(288));
     }
 
-    #[test]
-    fn test_part_1_with_complete_input() {
-        assert_eq!(part_1(COMPLETE_INPUT), Ok(3317888));
-    }
-
     #[test]
     fn test_parse_time_as_one_number() {
         let input = r#"Time:      7  15   30"#;
@@ -226,9 +251,4 @@ Distance:  9  40  200"#;
     fn test_part_2_with_test_input() {
         assert_eq!(part_2(TEST_INPUT), Ok(71503));
     }
-
-    #[test]
-    fn test_part_2_with_complete_input() {
-        assert_eq!(part_2(COMPLETE_INPUT), Ok(24655068));
-    }
 }
@@ -1,6 +1,6 @@
-use std::{cmp::Ordering, str::FromStr};
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
 
-#[derive(Debug, Eq, PartialEq, Hash)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
 pub struct Card {
     pub strength: u8,
     label: String,
@@ -68,6 +68,12 @@ impl FromStr for Card {
     }
 }
 
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
 impl Ord for Card {
     fn cmp(&self, other: &Self) -> Ordering {
         self.strength.cmp(&other.strength)
@@ -151,7 +157,7 @@ mod tests {
 
     #[test]
     fn test_card_sorting() {
-        let mut cards = vec![
+        let mut cards = [
             "4".parse::<Card>().unwrap(),
             "A".parse::<Card>().unwrap(),
             "T".parse::<Card>().unwrap(),
@@ -168,7 +174,7 @@ mod tests {
 
     #[test]
     fn test_is_stronger_than_with_joker() {
-        let cards = vec![
+        let cards = [
             "4".parse::<Card>().unwrap(),
             "J".parse::<Card>().unwrap(),
             "T".parse::<Card>().unwrap(),
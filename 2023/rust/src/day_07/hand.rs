@@ -1,8 +1,12 @@
 use crate::day_07::{card::Card, hand_type::HandType};
 
-use std::{cmp::Ordering, collections::HashMap};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::HashMap,
+    fmt::Display,
+};
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Hand {
     cards: Vec<Card>,
     bid: usize,
@@ -125,11 +129,11 @@ impl Hand {
             .filter(|(k, _)| !k.is_joker())
             .collect::<Vec<_>>();
 
-        if key_value_tuples.len() == 0 {
+        if key_value_tuples.is_empty() {
             return HandType::FiveOfAKind;
         }
 
-        key_value_tuples.sort_by(|a, b| b.1.cmp(&a.1));
+        key_value_tuples.sort_by_key(|(_, count)| Reverse(*count));
 
         key_value_tuples[0].1 += jokers;
 
@@ -172,6 +176,17 @@ impl Hand {
     }
 }
 
+/// Writes the hand as a line of the puzzle input.
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for card in &self.cards {
+            write!(f, "{}", card)?;
+        }
+
+        write!(f, " {}", self.bid)
+    }
+}
+
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.is_stronger_than(other) {
@@ -507,7 +522,7 @@ mod tests {
         };
         assert_eq!(hand.get_hand_type_with_joker(), HandType::FourOfAKind);
 
-        let hands = vec![
+        let hands = [
             Hand {
                 cards: "32TK3"
                     .to_string()
@@ -5,18 +5,52 @@ mod hand_type;
 use std::cmp::Ordering;
 
 use crate::day_07::card::Card;
+use crate::error::{parse_all, AocError};
+use crate::solution::{Answer, Solution};
 use hand::Hand;
 use nom::{
     character::complete::{alphanumeric1, line_ending, space1, u64},
+    error::{Error, ErrorKind},
     multi::separated_list1,
     sequence::separated_pair,
     IResult,
 };
 
-pub fn part_1(input: &str) -> usize {
-    let (_, mut hands) = parse_input(input).unwrap();
+pub fn part_1(input: &str) -> Result<usize, AocError> {
+    let hands = parse_all(input, parse_input)?;
 
+    Ok(get_total_winnings(hands))
+}
+
+pub fn part_2(input: &str) -> Result<usize, AocError> {
+    let hands = parse_all(input, parse_input)?;
+
+    Ok(get_total_winnings_with_joker(hands))
+}
+
+pub struct Day07 {
+    hands: Vec<Hand>,
+}
+
+impl Solution for Day07 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day07 {
+            hands: parse_all(input, parse_input)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(get_total_winnings(self.hands.clone())))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(get_total_winnings_with_joker(self.hands.clone())))
+    }
+}
+
+fn get_total_winnings(mut hands: Vec<Hand>) -> usize {
     hands.sort();
+
     hands
         .iter()
         .enumerate()
@@ -24,9 +58,7 @@ pub fn part_1(input: &str) -> usize {
         .sum()
 }
 
-pub fn part_2(input: &str) -> usize {
-    let (_, mut hands) = parse_input(input).unwrap();
-
+fn get_total_winnings_with_joker(mut hands: Vec<Hand>) -> usize {
     hands.sort_by(|a, b| {
         if a.is_stronger_than_with_joker(b) {
             return Ordering::Greater;
@@ -51,27 +83,42 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Hand>> {
 }
 
 fn parse_line(input: &str) -> IResult<&str, Hand> {
-    let (input, (cards, bid)) = separated_pair(alphanumeric1, space1, u64)(input)?;
+    let (remaining, (cards, bid)) = separated_pair(alphanumeric1, space1, u64)(input)?;
 
     let cards = cards
-        .chars()
-        .map(|c| Card::new(&c.to_string()).unwrap())
-        .collect();
+        .char_indices()
+        .map(|(i, c)| {
+            Card::new(&c.to_string())
+                .map_err(|_| nom::Err::Failure(Error::new(&input[i..], ErrorKind::Char)))
+        })
+        .collect::<Result<_, _>>()?;
 
-    let bid = bid.try_into().unwrap();
+    let Ok(bid) = bid.try_into() else {
+        return Err(nom::Err::Failure(Error::new(input, ErrorKind::TooLarge)));
+    };
 
     let hand = Hand::new(cards, bid);
 
-    Ok((input, hand))
+    Ok((remaining, hand))
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::generate::generate;
 
     const TEST_INPUT: &str = include_str!("../day_07/test_input.txt");
     const COMPLETE_INPUT: &str = include_str!("../day_07/complete_input.txt");
 
+    fn assert_display_round_trip(input: &str) {
+        let hands = parse_all(input, parse_input).unwrap();
+        let lines = hands.iter().map(|h| h.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(parse_all(&lines.join("\n"), parse_input), Ok(hands));
+    }
+
     #[test]
     fn test_parse_line() {
         let input = "32T3K 765";
@@ -101,21 +148,29 @@ mod tests {
 
     #[test]
     fn test_part_1_with_test_input() {
-        assert_eq!(part_1(TEST_INPUT), 6440);
+        assert_eq!(part_1(TEST_INPUT), Ok(6440));
     }
 
     #[test]
-    fn test_part_1_with_complete_input() {
-        assert_eq!(part_1(COMPLETE_INPUT), 247815719);
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT), Ok(5905));
     }
 
     #[test]
-    fn test_part_2_with_test_input() {
-        assert_eq!(part_2(TEST_INPUT), 5905);
+    fn test_display_round_trip() {
+        for input in [TEST_INPUT, COMPLETE_INPUT] {
+            assert_display_round_trip(input);
+
+            for line in input.lines() {
+                assert_eq!(parse_all(line, parse_line).unwrap().to_string(), line);
+            }
+        }
     }
 
-    #[test]
-    fn test_part_2_with_complete_input() {
-        assert_eq!(part_2(COMPLETE_INPUT), 248747492);
+    proptest! {
+        #[test]
+        fn test_display_round_trip_of_generated_hands(seed in any::<u64>(), size in 0..20usize) {
+            assert_display_round_trip(&generate(7, seed, size).unwrap());
+        }
     }
 }
@@ -1,154 +1,246 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, fmt::Display, rc::Rc};
 
 use instruction::Instruction;
 use node::Node;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_until},
-    character::complete::{line_ending, newline},
-    combinator::eof,
+    character::complete::{char, line_ending, newline},
+    combinator::{eof, value},
     multi::many1,
     sequence::pair,
     IResult,
 };
 use num::integer::lcm;
 
+use crate::error::{parse_all, AocError};
+use crate::solution::{Answer, Solution};
+
 mod instruction;
 mod node;
 
+/// The instructions and the nodes of the map, in the order of the input.
+#[derive(Debug)]
+struct Network {
+    instructions: Vec<Instruction>,
+    nodes: Vec<Rc<RefCell<Node>>>,
+}
+
+/// Writes the network as the puzzle input.
+impl Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for instruction in &self.instructions {
+            write!(f, "{}", instruction)?;
+        }
+        writeln!(f)?;
+        writeln!(f)?;
+
+        for node in &self.nodes {
+            writeln!(f, "{}", node.borrow())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Nodes link to each other, so they are compared by the lines they are written as, which
+/// name the nodes they link to instead of following them.
+impl PartialEq for Network {
+    fn eq(&self, other: &Self) -> bool {
+        let lines = |network: &Network| {
+            network
+                .nodes
+                .iter()
+                .map(|node| node.borrow().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        self.instructions == other.instructions && lines(self) == lines(other)
+    }
+}
+
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
 
-pub fn part_1(input: &str) -> usize {
-    let (_, (instructions, nodes)) = match parse_input(input) {
-        Ok((input, instructions)) => (input, instructions),
-        Err(e) => panic!("Error: {:?}", e),
-    };
+pub fn part_1(input: &str) -> Result<usize, AocError> {
+    let Network {
+        instructions,
+        nodes,
+    } = parse_all(input, parse_input)?;
 
-    follow_instructions(instructions, connect_nodes(nodes))
+    follow_instructions(&instructions, &connect_nodes(nodes)?)
 }
 
-pub fn part_2(input: &str) -> usize {
-    let (_, (instructions, nodes)) = match parse_input(input) {
-        Ok((input, instructions)) => (input, instructions),
-        Err(e) => panic!("Error: {:?}", e),
-    };
+pub fn part_2(input: &str) -> Result<usize, AocError> {
+    let Network {
+        instructions,
+        nodes,
+    } = parse_all(input, parse_input)?;
+
+    follow_instructions_as_ghost(&instructions, &connect_nodes(nodes)?)
+}
+
+pub struct Day08 {
+    instructions: Vec<Instruction>,
+    nodes: Vec<Rc<RefCell<Node>>>,
+}
 
-    follow_instructions_as_ghost(instructions, connect_nodes(nodes))
+impl Solution for Day08 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let Network {
+            instructions,
+            nodes,
+        } = parse_all(input, parse_input)?;
+
+        Ok(Day08 {
+            instructions,
+            nodes: connect_nodes(nodes)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(follow_instructions(
+            &self.instructions,
+            &self.nodes,
+        )?))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(follow_instructions_as_ghost(
+            &self.instructions,
+            &self.nodes,
+        )?))
+    }
 }
 
-fn connect_nodes(nodes: Vec<Rc<RefCell<Node>>>) -> Vec<Rc<RefCell<Node>>> {
+fn connect_nodes(nodes: Vec<Rc<RefCell<Node>>>) -> Result<Vec<Rc<RefCell<Node>>>, AocError> {
+    let find_node = |id: &str, from: &Rc<RefCell<Node>>| {
+        nodes
+            .iter()
+            .find(|n| n.borrow().get_id() == id)
+            .cloned()
+            .ok_or_else(|| {
+                AocError::Invalid(format!(
+                    "node {} points to unknown node {}",
+                    from.borrow().get_id(),
+                    id
+                ))
+            })
+    };
+
     for node in nodes.iter() {
         let left = node.borrow().get_left();
         let right = node.borrow().get_right();
 
         if let Some(left) = left {
             let left_id = left.borrow().get_id().to_string();
-            let left_node = nodes
-                .iter()
-                .find(|n| n.borrow().get_id() == left_id)
-                .unwrap();
-            node.borrow_mut().set_left(left_node.clone());
+            let left_node = find_node(&left_id, node)?;
+            node.borrow_mut().set_left(left_node);
         }
 
         if let Some(right) = right {
             let right_id = right.borrow().get_id().to_string();
-            let right_node = nodes
-                .iter()
-                .find(|n| n.borrow().get_id() == right_id)
-                .unwrap();
-            node.borrow_mut().set_right(right_node.clone());
+            let right_node = find_node(&right_id, node)?;
+            node.borrow_mut().set_right(right_node);
         }
     }
 
-    nodes
+    Ok(nodes)
 }
 
-fn follow_instructions(instructions: Vec<Instruction>, nodes: Vec<Rc<RefCell<Node>>>) -> usize {
-    let mut node = nodes
+fn follow_instructions(
+    instructions: &[Instruction],
+    nodes: &[Rc<RefCell<Node>>],
+) -> Result<usize, AocError> {
+    let node = nodes
         .iter()
         .find(|n| n.borrow().is_start())
-        .unwrap()
-        .clone();
-    let mut steps = 0;
-
-    loop {
-        if node.borrow().is_end() {
-            break;
-        }
-
-        let instruction = &instructions[steps % instructions.len()];
-        let next_node = node.borrow().take(instruction);
-
-        if next_node.is_none() {
-            // being none it means that it is connected to itself, which we avoid in the data
-            // structure to avoid cyclic references and we emulate connecting to itself by
-            // not updating the node
-            continue;
-        }
-
-        node = next_node.unwrap();
-        steps += 1;
-    }
+        .ok_or(AocError::Invalid("there is no AAA node".to_string()))?;
 
-    steps
+    walk(node.clone(), instructions, nodes.len(), |n| n.is_end())
 }
 
 fn follow_instructions_as_ghost(
-    instructions: Vec<Instruction>,
-    nodes: Vec<Rc<RefCell<Node>>>,
-) -> usize {
+    instructions: &[Instruction],
+    nodes: &[Rc<RefCell<Node>>],
+) -> Result<usize, AocError> {
     nodes
         .iter()
         .filter(|n| n.borrow().is_start_for_ghost())
-        .map(|n| follow_instructions_as_ghost_for_node(n.clone(), &instructions))
-        .fold(1, |acc, x| lcm(acc, x))
+        .map(|n| {
+            walk(n.clone(), instructions, nodes.len(), |n| {
+                n.is_end_for_ghost()
+            })
+        })
+        .try_fold(1, |acc, steps| Ok(lcm(acc, steps?)))
 }
 
-fn follow_instructions_as_ghost_for_node(
+/// Follows the instructions from `node` until `is_end` holds. A walk is a sequence of
+/// (node, instruction index) states, so if it goes on for longer than there are states it
+/// is looping without ever reaching an end.
+fn walk<F>(
     node: Rc<RefCell<Node>>,
-    instructions: &Vec<Instruction>,
-) -> usize {
+    instructions: &[Instruction],
+    total_nodes: usize,
+    is_end: F,
+) -> Result<usize, AocError>
+where
+    F: Fn(&Node) -> bool,
+{
+    let max_steps = total_nodes * instructions.len();
     let mut current_node = node;
     let mut steps = 0;
 
-    loop {
-        if current_node.borrow().is_end_for_ghost() {
-            break;
+    while !is_end(&current_node.borrow()) {
+        if steps > max_steps {
+            return Err(AocError::NoSolution(format!(
+                "node {} never reaches an end",
+                current_node.borrow().get_id()
+            )));
         }
+
         let instruction = &instructions[steps % instructions.len()];
         let next_node = current_node.borrow().take(instruction);
 
-        if next_node.is_none() {
-            break;
+        // being none it means that it is connected to itself, which we avoid in the data
+        // structure to avoid cyclic references and we emulate connecting to itself by
+        // not updating the node
+        if let Some(next_node) = next_node {
+            current_node = next_node;
         }
 
-        current_node = next_node.unwrap();
         steps += 1;
     }
 
-    steps
+    Ok(steps)
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<Instruction>, Vec<Rc<RefCell<Node>>>)> {
+fn parse_input(input: &str) -> IResult<&str, Network> {
     let (input, instructions) = parse_instructions(input)?;
     let (input, nodes) = parse_node_lines(input)?;
 
-    Ok((input, (instructions, nodes)))
+    Ok((
+        input,
+        Network {
+            instructions,
+            nodes,
+        },
+    ))
 }
 
 fn parse_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
-    let (input, instructions) = take_until("\n\n")(input)?;
+    let (input, instructions) = many1(parse_instruction)(input)?;
     let (input, _) = pair(newline, newline)(input)?;
 
-    Ok((
-        input,
-        instructions
-            .chars()
-            .map(|c| c.to_string().parse::<Instruction>().unwrap())
-            .collect(),
-    ))
+    Ok((input, instructions))
+}
+
+fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
+    alt((
+        value(Instruction::Right, char('R')),
+        value(Instruction::Left, char('L')),
+    ))(input)
 }
 
 fn parse_node_lines(input: &str) -> IResult<&str, Vec<Rc<RefCell<Node>>>> {
@@ -178,13 +270,26 @@ fn parse_node_line(line: &str) -> IResult<&str, Rc<RefCell<Node>>> {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::generate::generate;
 
     const TEST_INPUT_1: &str = include_str!("../day_08/test_input_1.txt");
     const TEST_INPUT_2: &str = include_str!("../day_08/test_input_2.txt");
     const TEST_INPUT_3: &str = include_str!("../day_08/test_input_3.txt");
     const COMPLETE_INPUT: &str = include_str!("../day_08/complete_input.txt");
 
+    #[test]
+    fn test_display_round_trip() {
+        for input in [TEST_INPUT_1, TEST_INPUT_2, TEST_INPUT_3, COMPLETE_INPUT] {
+            let network = parse_all(input, parse_input).unwrap();
+
+            assert_eq!(network.to_string().trim_end(), input.trim_end());
+            assert_eq!(parse_all(&network.to_string(), parse_input), Ok(network));
+        }
+    }
+
     #[test]
     fn test_parse_instructions() {
         let input = "RL\n\n";
@@ -253,7 +358,13 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let (input, (instructions, nodes)) = parse_input(TEST_INPUT_1).unwrap();
+        let (
+            input,
+            Network {
+                instructions,
+                nodes,
+            },
+        ) = parse_input(TEST_INPUT_1).unwrap();
         assert_eq!(input, "");
         assert_eq!(instructions, vec![Instruction::Right, Instruction::Left]);
         assert_eq!(nodes.len(), 7);
@@ -279,7 +390,13 @@ mod tests {
         assert!(nodes[6].borrow().get_left().is_none());
         assert!(nodes[6].borrow().get_right().is_none());
 
-        let (input, (instructions, nodes)) = parse_input(TEST_INPUT_2).unwrap();
+        let (
+            input,
+            Network {
+                instructions,
+                nodes,
+            },
+        ) = parse_input(TEST_INPUT_2).unwrap();
         assert_eq!(input, "");
         assert_eq!(
             instructions,
@@ -311,22 +428,140 @@ mod tests {
 
     #[test]
     fn test_part_1_with_test_input() {
-        assert_eq!(part_1(TEST_INPUT_1), 2);
-        assert_eq!(part_1(TEST_INPUT_2), 6);
+        assert_eq!(part_1(TEST_INPUT_1), Ok(2));
+        assert_eq!(part_1(TEST_INPUT_2), Ok(6));
     }
 
     #[test]
-    fn test_part_1_with_complete_input() {
-        assert_eq!(part_1(COMPLETE_INPUT), 18673);
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT_3), Ok(6));
     }
 
     #[test]
-    fn test_part_2_with_test_input() {
-        assert_eq!(part_2(TEST_INPUT_3), 6);
+    fn test_unknown_node() {
+        let input = "L\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+
+        assert_eq!(
+            part_1(input),
+            Err(AocError::Invalid(
+                "node AAA points to unknown node BBB".to_string()
+            ))
+        );
     }
 
     #[test]
-    fn test_part_2_with_complete_input() {
-        assert_eq!(part_2(COMPLETE_INPUT), 17_972_669_116_327);
+    fn test_invalid_instruction() {
+        assert_eq!(
+            part_1("LRX\n\nAAA = (ZZZ, ZZZ)\n"),
+            Err(AocError::Parse {
+                line: 1,
+                column: 3,
+                kind: nom::error::ErrorKind::Char
+            })
+        );
+    }
+
+    /// A network where every ghost walks a ring of its own, which takes it to its end node
+    /// in `laps` times the length of the instructions and then round to it again in as many
+    /// steps: the shape of the puzzle inputs that taking the least common multiple relies on.
+    /// The side of a node the instructions never take leads to the node `others` picks.
+    fn ring_network(goes_left: &[bool], laps: &[usize], others: &[usize]) -> String {
+        let rings = laps
+            .iter()
+            .enumerate()
+            .map(|(ghost, laps)| {
+                let mut ring = vec![format!("{}A", ghost), format!("{}Z", ghost)];
+                ring.extend((1..laps * goes_left.len()).map(|i| format!("{}N{}", ghost, i)));
+                ring
+            })
+            .collect::<Vec<_>>();
+        let all_names = rings.iter().flatten().collect::<Vec<_>>();
+
+        let mut lines = vec![];
+        let mut connect = |node: &str, next: &str, step: usize| {
+            let other = all_names[others[lines.len() % others.len()] % all_names.len()];
+            let (left, right) = if goes_left[step % goes_left.len()] {
+                (next, other.as_str())
+            } else {
+                (other.as_str(), next)
+            };
+
+            lines.push(format!("{} = ({}, {})", node, left, right));
+        };
+
+        for ring in rings.iter() {
+            let round = ring.len() - 1;
+
+            for step in 0..round {
+                let node = if step == 0 { &ring[0] } else { &ring[step + 1] };
+                connect(node, &ring[(step + 1) % round + 1], step);
+            }
+            connect(&ring[1], &ring[1 % round + 1], 0);
+        }
+
+        let instructions = goes_left
+            .iter()
+            .map(|&left| if left { 'L' } else { 'R' })
+            .collect::<String>();
+
+        format!("{}\n\n{}\n", instructions, lines.join("\n"))
+    }
+
+    /// Moves all the ghosts at once until they all are at an end node.
+    fn simulate_ghosts(instructions: &[Instruction], nodes: &[Rc<RefCell<Node>>]) -> usize {
+        let mut ghosts = nodes
+            .iter()
+            .filter(|n| n.borrow().is_start_for_ghost())
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut steps = 0;
+
+        while !ghosts.iter().all(|n| n.borrow().is_end_for_ghost()) {
+            let instruction = &instructions[steps % instructions.len()];
+            for ghost in ghosts.iter_mut() {
+                let next_node = ghost.borrow().take(instruction);
+                if let Some(next_node) = next_node {
+                    *ghost = next_node;
+                }
+            }
+
+            steps += 1;
+        }
+
+        steps
+    }
+
+    proptest! {
+        /// The least common multiple of the walks of every ghost is when they first are all
+        /// at an end node together.
+        #[test]
+        fn test_ghost_walks_match_simulation(
+            goes_left in prop::collection::vec(any::<bool>(), 1..4),
+            laps in prop::collection::vec(1..6usize, 1..4),
+            others in prop::collection::vec(any::<usize>(), 1..8),
+        ) {
+            let input = ring_network(&goes_left, &laps, &others);
+            let Network {
+                instructions,
+                nodes,
+            } = parse_all(&input, parse_input).unwrap();
+            let nodes = connect_nodes(nodes).unwrap();
+
+            prop_assert_eq!(
+                follow_instructions_as_ghost(&instructions, &nodes),
+                Ok(simulate_ghosts(&instructions, &nodes))
+            );
+        }
+
+        #[test]
+        fn test_display_round_trip_of_generated_networks(seed in any::<u64>(), size in 0..40usize) {
+            let network = parse_all(&generate(8, seed, size).unwrap(), parse_input).unwrap();
+            let network = Network {
+                nodes: connect_nodes(network.nodes).unwrap(),
+                ..network
+            };
+
+            prop_assert_eq!(parse_all(&network.to_string(), parse_input), Ok(network));
+        }
     }
 }
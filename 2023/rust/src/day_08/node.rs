@@ -72,20 +72,20 @@ impl Node {
     }
 }
 
+/// Writes the node as a line of the puzzle input, a missing side leading to the node itself.
 impl Display for Node {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let left = self.get_left();
-        let mut left_id = "None".to_string();
-        if left.is_some() {
-            left_id = left.unwrap().borrow().id.clone();
-        }
-
-        let right = self.get_right();
-        let mut right_id = "None".to_string();
-        if right.is_some() {
-            right_id = right.unwrap().borrow().id.clone();
-        }
-
-        write!(f, "{} -> ({:?}, {:?})", self.id, left_id, right_id)
+        let id = |side: Option<Rc<RefCell<Node>>>| match side {
+            Some(node) => node.borrow().id.clone(),
+            None => self.id.clone(),
+        };
+
+        write!(
+            f,
+            "{} = ({}, {})",
+            self.id,
+            id(self.get_left()),
+            id(self.get_right())
+        )
     }
 }
@@ -1,3 +1,5 @@
+use std::io::BufRead;
+
 use nom::{
     branch::alt,
     character::complete::{i64, line_ending, space0},
@@ -7,33 +9,82 @@ use nom::{
     IResult,
 };
 
-pub fn part_1(input: &str) -> i64 {
-    let Ok((_, oasis_report)) = parse_input(input) else {
-        panic!("Failed to parse input: {:?}", input);
-    };
+use crate::error::{parse_all, AocError};
+use crate::input::for_each_line;
+use crate::solution::{Answer, Solution, Streaming};
 
-    oasis_report
-        .into_iter()
-        .map(|x| extrapolate_forward(x))
-        .sum()
+/// Sums the next values of the report lines read from `reader`, one line at a time.
+pub fn part_1<R: BufRead>(reader: R) -> Result<i64, AocError> {
+    sum_extrapolations(reader, extrapolate_forward)
 }
 
 fn extrapolate_forward(report_line: Vec<i64>) -> i64 {
     let mut iterations = process_report_line(report_line);
     iterations.last_mut().unwrap().push(0);
 
-    iterations.iter().map(|x| x.iter().last().unwrap()).sum()
+    iterations.iter().map(|x| x.last().unwrap()).sum()
 }
 
-pub fn part_2(input: &str) -> i64 {
-    let Ok((_, oasis_report)) = parse_input(input) else {
-        panic!("Failed to parse input: {:?}", input);
-    };
+/// Sums the previous values of the report lines read from `reader`, one line at a time.
+pub fn part_2<R: BufRead>(reader: R) -> Result<i64, AocError> {
+    sum_extrapolations(reader, extrapolate_backwards)
+}
 
-    oasis_report
-        .into_iter()
-        .map(|x| extrapolate_backwards(x))
-        .sum()
+fn sum_extrapolations<R: BufRead>(
+    reader: R,
+    extrapolate: fn(Vec<i64>) -> i64,
+) -> Result<i64, AocError> {
+    let mut sum = 0;
+    for_each_line(reader, |i, line| {
+        let report_line = parse_all(line, many1(parse_number)).map_err(|e| e.on_line(i))?;
+        sum += extrapolate(report_line);
+
+        Ok(())
+    })?;
+
+    Ok(sum)
+}
+
+pub struct Day09 {
+    oasis_report: Vec<Vec<i64>>,
+}
+
+impl Solution for Day09 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        let oasis_report = parse_all(input, parse_input)?;
+
+        Ok(Day09 { oasis_report })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.oasis_report
+                .iter()
+                .cloned()
+                .map(extrapolate_forward)
+                .sum::<i64>(),
+        ))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.oasis_report
+                .iter()
+                .cloned()
+                .map(extrapolate_backwards)
+                .sum::<i64>(),
+        ))
+    }
+}
+
+impl Streaming for Day09 {
+    fn stream_part_one(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_1(reader)?))
+    }
+
+    fn stream_part_two(reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        Ok(Box::new(part_2(reader)?))
+    }
 }
 
 fn extrapolate_backwards(report_line: Vec<i64>) -> i64 {
@@ -59,7 +110,7 @@ fn process_report_line(report_line: Vec<i64>) -> Vec<Vec<i64>> {
     iterations
 }
 
-fn process_report_line_iteration(report_line_iteration: &Vec<i64>) -> Vec<i64> {
+fn process_report_line_iteration(report_line_iteration: &[i64]) -> Vec<i64> {
     report_line_iteration
         .windows(2)
         .map(|w| w[1] - w[0])
@@ -84,7 +135,6 @@ mod tests {
     use super::*;
 
     const TEST_INPUT: &str = include_str!("../day_09/test_input.txt");
-    const COMPLETE_INPUT: &str = include_str!("../day_09/complete_input.txt");
 
     #[test]
     fn test_parse_line() {
@@ -137,21 +187,11 @@ mod tests {
 
     #[test]
     fn test_part_1_with_test_input() {
-        assert_eq!(part_1(TEST_INPUT), 114);
-    }
-
-    #[test]
-    fn test_part_1_with_complete_input() {
-        assert_eq!(part_1(COMPLETE_INPUT), 2101499000);
+        assert_eq!(part_1(TEST_INPUT.as_bytes()), Ok(114));
     }
 
     #[test]
     fn test_part_2_with_test_input() {
-        assert_eq!(part_2(TEST_INPUT), 2);
-    }
-
-    #[test]
-    fn test_part_2_with_complete_input() {
-        assert_eq!(part_2(COMPLETE_INPUT), 1089);
+        assert_eq!(part_2(TEST_INPUT.as_bytes()), Ok(2));
     }
 }
@@ -1,171 +1,118 @@
 mod direction;
 mod pipe;
 
-use std::{cell::RefCell, rc::Rc};
-
 use direction::Direction;
-use pipe::Pipe;
-use pipe::PipeStatus;
+use pipe::PipeType;
+
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input: &str) -> usize {
-    let pipes = connect_pipes(parse_input(input));
-    let start = pipes.iter().find(|pipe| pipe.borrow().is_start()).unwrap();
+pub fn part_1(input: &str) -> Result<usize, AocError> {
+    let pipes = input.parse()?;
 
-    find_steps_to_farthest_pipe(start)
+    find_steps_to_farthest_pipe(&pipes)
 }
 
-pub fn part_2(input: &str) -> usize {
-    let pipes = connect_pipes(parse_input(input));
+pub fn part_2(input: &str) -> Result<usize, AocError> {
+    let pipes = input.parse()?;
 
     count_enclosed(&pipes)
 }
 
-fn find_steps_to_farthest_pipe(start: &Rc<RefCell<Pipe>>) -> usize {
-    let mut steps = 1;
-    let connected_pipes = start.borrow().get_connected_pipes();
-    let mut path_a = (connected_pipes[0].0, connected_pipes[0].1.clone());
-    let mut path_b = (connected_pipes[1].0, connected_pipes[1].1.clone());
+pub struct Day10 {
+    pipes: Grid<PipeType>,
+}
 
-    loop {
-        if path_a
-            .1
-            .upgrade()
-            .unwrap()
-            .borrow()
-            .is_at(path_b.1.upgrade().unwrap().borrow().get_coordinates())
-        {
-            break;
-        }
+impl Solution for Day10 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day10 {
+            pipes: input.parse()?,
+        })
+    }
 
-        let next_path_a = path_a
-            .1
-            .upgrade()
-            .unwrap()
-            .borrow()
-            .traverse_from(&path_a.0);
-        let next_path_b = path_b
-            .1
-            .upgrade()
-            .unwrap()
-            .borrow()
-            .traverse_from(&path_b.0);
-
-        path_a = (next_path_a.clone().unwrap().0, next_path_a.unwrap().1);
-        path_b = (next_path_b.clone().unwrap().0, next_path_b.unwrap().1);
-
-        steps += 1;
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(find_steps_to_farthest_pipe(&self.pipes)?))
     }
 
-    steps
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(count_enclosed(&self.pipes)?))
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Rc<RefCell<Pipe>>> {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().map(move |(x, c)| {
-                let pipe_type = c.to_string().parse().unwrap();
-                Pipe::new(pipe_type, x, y)
-            })
-        })
-        .collect()
+fn find_steps_to_farthest_pipe(pipes: &Grid<PipeType>) -> Result<usize, AocError> {
+    Ok(find_main_loop(pipes)?.len() / 2)
 }
 
-fn connect_pipes(pipes: Vec<Rc<RefCell<Pipe>>>) -> Vec<Rc<RefCell<Pipe>>> {
-    pipes.iter().for_each(|pipe| {
-        let (x, y) = pipe.borrow().get_coordinates();
-
-        if y > 0 {
-            let north_coords = (x, y - 1);
-            if let Some(north) = pipes
-                .iter()
-                .find(|pipe| pipe.borrow().is_at(north_coords))
-                .cloned()
-            {
-                pipe.borrow_mut().set_pipe(&Direction::North, north);
-            }
-        }
-
-        let east_coords = (x + 1, y);
-        if let Some(east) = pipes
-            .iter()
-            .find(|pipe| pipe.borrow().is_at(east_coords))
-            .cloned()
-        {
-            pipe.borrow_mut().set_pipe(&Direction::East, east);
-        }
-
-        let south_coords = (x, y + 1);
-        if let Some(south) = pipes
-            .iter()
-            .find(|pipe| pipe.borrow().is_at(south_coords))
-            .cloned()
-        {
-            pipe.borrow_mut().set_pipe(&Direction::South, south);
-        }
+/// Counts the tiles enclosed by the main loop: the shoelace formula gives the area inside
+/// the loop and Pick's theorem takes away the tiles the loop itself goes through.
+fn count_enclosed(pipes: &Grid<PipeType>) -> Result<usize, AocError> {
+    let main_loop = find_main_loop(pipes)?;
 
-        if x > 0 {
-            let west_coords = (x - 1, y);
-            if let Some(west) = pipes
-                .iter()
-                .find(|pipe| pipe.borrow().is_at(west_coords))
-                .cloned()
-            {
-                pipe.borrow_mut().set_pipe(&Direction::West, west);
-            }
-        }
-    });
+    let double_area = main_loop
+        .iter()
+        .zip(main_loop.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| (x1 * y2) as isize - (x2 * y1) as isize)
+        .sum::<isize>()
+        .unsigned_abs();
 
-    pipes
+    Ok((double_area + 2 - main_loop.len()) / 2)
 }
 
-fn count_enclosed(pipes: &Vec<Rc<RefCell<Pipe>>>) -> usize {
-    let start = pipes.iter().find(|pipe| pipe.borrow().is_start()).unwrap();
-    for pipe in start.borrow().get_loop().into_iter() {
-        if let Ok(mut pipe) = pipe.try_borrow_mut() {
-            pipe.set_status(PipeStatus::MainLoop);
-        }
+/// The positions of the pipes in the main loop, in order, beginning with the start pipe.
+fn find_main_loop(pipes: &Grid<PipeType>) -> Result<Vec<(usize, usize)>, AocError> {
+    let start = pipes
+        .position(|pipe| *pipe == PipeType::Start)
+        .ok_or(AocError::Invalid("there is no start pipe".to_string()))?;
+
+    let directions = find_start_directions(pipes, start);
+    if directions.len() != 2 {
+        return Err(AocError::Invalid(format!(
+            "the start pipe connects to {} pipes instead of 2",
+            directions.len()
+        )));
     }
 
-    let mut enclosed = 0;
+    let mut main_loop = vec![start];
+    let mut position = start;
+    let mut direction = directions[0];
 
-    let mut main_loop = vec![start.clone()];
-    main_loop.append(&mut start.borrow().get_loop());
-    let main_loop = main_loop;
+    loop {
+        position = pipes.step(position, direction.offset()).ok_or_else(|| {
+            AocError::Invalid(format!("the loop leaves the grid at {:?}", position))
+        })?;
 
-    for pipe in pipes {
-        if pipe.borrow().is_in_main_loop() {
-            continue;
+        if position == start {
+            return Ok(main_loop);
         }
 
-        if is_enclosed(pipe.clone(), &main_loop) {
-            enclosed += 1;
-        }
-    }
+        direction = pipes[position]
+            .exit(&direction.opposite())
+            .ok_or_else(|| AocError::Invalid(format!("the loop breaks at {:?}", position)))?;
 
-    enclosed
+        main_loop.push(position);
+    }
 }
 
-fn is_enclosed(pipe: Rc<RefCell<Pipe>>, main_loop: &Vec<Rc<RefCell<Pipe>>>) -> bool {
-    println!("checking if pipe {} is enclosed", pipe.borrow());
-
-    let (x, y) = pipe.borrow().get_coordinates();
-    let mut enclosed = false;
-    main_loop.windows(2).for_each(|pair| {
-        let (_, y1) = pair[0].borrow().get_coordinates();
-        let (x2, y2) = pair[1].borrow().get_coordinates();
-        if (y2 > y) != (y1 > y) && x < x2 {
-            enclosed = !enclosed;
-        }
-    });
-
-    enclosed
+/// The start pipe hides its shape, so it goes wherever a neighbour connects back to it.
+fn find_start_directions(pipes: &Grid<PipeType>, start: (usize, usize)) -> Vec<Direction> {
+    Direction::ALL
+        .into_iter()
+        .filter(|direction| {
+            pipes
+                .step(start, direction.offset())
+                .is_some_and(|neighbour| pipes[neighbour].is_connected_to(&direction.opposite()))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use nom::error::ErrorKind;
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::generate::generate;
 
     const TEST_INPUT_1: &str = include_str!("../day_10/test-input-1.txt");
     const TEST_INPUT_2: &str = include_str!("../day_10/test-input-2.txt");
@@ -177,119 +124,109 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let expected = vec![
-            Pipe::new(".".parse().unwrap(), 0, 0),
-            Pipe::new(".".parse().unwrap(), 1, 0),
-            Pipe::new(".".parse().unwrap(), 2, 0),
-            Pipe::new(".".parse().unwrap(), 3, 0),
-            Pipe::new(".".parse().unwrap(), 4, 0),
-            Pipe::new(".".parse().unwrap(), 0, 1),
-            Pipe::new("S".parse().unwrap(), 1, 1),
-            Pipe::new("-".parse().unwrap(), 2, 1),
-            Pipe::new("7".parse().unwrap(), 3, 1),
-            Pipe::new(".".parse().unwrap(), 4, 1),
-            Pipe::new(".".parse().unwrap(), 0, 2),
-            Pipe::new("|".parse().unwrap(), 1, 2),
-            Pipe::new(".".parse().unwrap(), 2, 2),
-            Pipe::new("|".parse().unwrap(), 3, 2),
-            Pipe::new(".".parse().unwrap(), 4, 2),
-            Pipe::new(".".parse().unwrap(), 0, 3),
-            Pipe::new("L".parse().unwrap(), 1, 3),
-            Pipe::new("-".parse().unwrap(), 2, 3),
-            Pipe::new("J".parse().unwrap(), 3, 3),
-            Pipe::new(".".parse().unwrap(), 4, 3),
-            Pipe::new(".".parse().unwrap(), 0, 4),
-            Pipe::new(".".parse().unwrap(), 1, 4),
-            Pipe::new(".".parse().unwrap(), 2, 4),
-            Pipe::new(".".parse().unwrap(), 3, 4),
-            Pipe::new(".".parse().unwrap(), 4, 4),
-        ];
-        assert_eq!(parse_input(TEST_INPUT_1), expected);
+        let pipes = TEST_INPUT_1.parse::<Grid<PipeType>>().unwrap();
+
+        assert_eq!(pipes.size(), (5, 5));
+        assert_eq!(pipes.get((0, 0)), Some(&PipeType::Ground));
+        assert_eq!(pipes.get((1, 1)), Some(&PipeType::Start));
+        assert_eq!(pipes.get((2, 1)), Some(&PipeType::EW));
+        assert_eq!(pipes.get((3, 1)), Some(&PipeType::SW));
+        assert_eq!(pipes.get((1, 2)), Some(&PipeType::NS));
+        assert_eq!(pipes.get((1, 3)), Some(&PipeType::NE));
+        assert_eq!(pipes.get((3, 3)), Some(&PipeType::NW));
     }
 
     #[test]
-    fn test_connect_pipes() {
-        let pipes = parse_input(TEST_INPUT_1);
-        let pipes = connect_pipes(pipes);
-
-        let start = pipes.iter().find(|pipe| pipe.borrow().is_start()).unwrap();
-        let start = start.borrow();
+    fn test_find_start_directions() {
+        let pipes = TEST_INPUT_1.parse::<Grid<PipeType>>().unwrap();
 
-        assert!(start.goes(&Direction::East));
-        assert!(start.goes(&Direction::South));
-
-        assert!(!start.goes(&Direction::North));
-        assert!(!start.goes(&Direction::West));
+        assert_eq!(
+            find_start_directions(&pipes, (1, 1)),
+            vec![Direction::East, Direction::South]
+        );
     }
 
     #[test]
-    fn test_follow_connected_pipes() {
-        let pipes = parse_input(TEST_INPUT_1);
-        let pipes = connect_pipes(pipes);
-
-        let start = pipes.iter().find(|pipe| pipe.borrow().is_start()).unwrap();
-        let start = start.borrow();
-
-        let connected_pipes = start.get_connected_pipes();
-        assert_eq!(connected_pipes.len(), 2);
-
-        let (path_a, path_b) = (&connected_pipes[0], &connected_pipes[1]);
-        assert!(path_a.1.upgrade().unwrap().borrow().is_at((2, 1)));
-        assert!(path_b.1.upgrade().unwrap().borrow().is_at((1, 2)));
-
-        let (path_a, path_b) = (
-            path_a
-                .1
-                .upgrade()
-                .unwrap()
-                .borrow()
-                .traverse_from(&path_a.0),
-            path_b
-                .1
-                .upgrade()
-                .unwrap()
-                .borrow()
-                .traverse_from(&path_b.0),
+    fn test_find_main_loop() {
+        let pipes = TEST_INPUT_1.parse::<Grid<PipeType>>().unwrap();
+
+        assert_eq!(
+            find_main_loop(&pipes),
+            Ok(vec![
+                (1, 1),
+                (2, 1),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (2, 3),
+                (1, 3),
+                (1, 2)
+            ])
         );
-
-        assert!(path_a.unwrap().1.upgrade().unwrap().borrow().is_at((3, 1)));
-        assert!(path_b.unwrap().1.upgrade().unwrap().borrow().is_at((1, 3)));
     }
 
     #[test]
     fn test_find_steps_to_farthest_pipe() {
-        let pipes = connect_pipes(parse_input(TEST_INPUT_1));
-        let start = pipes.iter().find(|pipe| pipe.borrow().is_start()).unwrap();
-        assert_eq!(find_steps_to_farthest_pipe(start), 4);
+        let pipes = TEST_INPUT_1.parse().unwrap();
+        assert_eq!(find_steps_to_farthest_pipe(&pipes), Ok(4));
 
-        let pipes = connect_pipes(parse_input(TEST_INPUT_2));
-        let start = pipes.iter().find(|pipe| pipe.borrow().is_start()).unwrap();
-        assert_eq!(find_steps_to_farthest_pipe(start), 8);
+        let pipes = TEST_INPUT_2.parse().unwrap();
+        assert_eq!(find_steps_to_farthest_pipe(&pipes), Ok(8));
     }
 
     #[test]
     fn test_part_1_with_test_input() {
-        assert_eq!(part_1(TEST_INPUT_1), 4);
-        assert_eq!(part_1(TEST_INPUT_2), 8);
+        assert_eq!(part_1(TEST_INPUT_1), Ok(4));
+        assert_eq!(part_1(TEST_INPUT_2), Ok(8));
     }
 
     #[test]
-    fn test_part_1_with_complete_input() {
-        assert_eq!(part_1(COMPLETE_INPUT), 6927);
+    fn test_part_2_with_test_input() {
+        assert_eq!(part_2(TEST_INPUT_1), Ok(1));
+        assert_eq!(part_2(TEST_INPUT_2), Ok(1));
+        assert_eq!(part_2(TEST_INPUT_3), Ok(4));
+        assert_eq!(part_2(TEST_INPUT_4), Ok(8));
+        assert_eq!(part_2(TEST_INPUT_5), Ok(4));
+        assert_eq!(part_2(TEST_INPUT_6), Ok(10));
+        assert_eq!(part_2("S7\nLJ"), Ok(0));
     }
 
     #[test]
-    fn test_part_2_with_test_input() {
-        assert_eq!(part_2(TEST_INPUT_1), 1);
-        assert_eq!(part_2(TEST_INPUT_2), 1);
-        assert_eq!(part_2(TEST_INPUT_3), 4);
-        assert_eq!(part_2(TEST_INPUT_4), 8);
-        assert_eq!(part_2(TEST_INPUT_5), 4);
-        assert_eq!(part_2(TEST_INPUT_6), 10);
+    fn test_invalid_pipe() {
+        assert_eq!(
+            part_1(".....\n.S-7.\n.|.|.\n.L-X.\n....."),
+            Err(AocError::Parse {
+                line: 4,
+                column: 4,
+                kind: ErrorKind::Char
+            })
+        );
     }
 
     #[test]
-    fn test_part_2_with_complete_input() {
-        assert_eq!(part_2(COMPLETE_INPUT), 467);
+    fn test_display_round_trip() {
+        for input in [
+            TEST_INPUT_1,
+            TEST_INPUT_2,
+            TEST_INPUT_3,
+            TEST_INPUT_4,
+            TEST_INPUT_5,
+            TEST_INPUT_6,
+            COMPLETE_INPUT,
+        ] {
+            let pipes = input.parse::<Grid<PipeType>>().unwrap();
+
+            assert_eq!(pipes.to_string().trim_end(), input.trim_end());
+            assert_eq!(pipes.to_string().parse(), Ok(pipes));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_display_round_trip_of_generated_pipes(seed in any::<u64>(), size in 0..20usize) {
+            let pipes = generate(10, seed, size).unwrap().parse::<Grid<PipeType>>().unwrap();
+
+            prop_assert_eq!(pipes.to_string().parse(), Ok(pipes));
+        }
     }
 }
@@ -2,7 +2,7 @@ use std::{fmt::Display, str::FromStr};
 
 use crate::day_10::direction::Direction;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PipeType {
     NS,
     EW,
@@ -32,6 +32,14 @@ impl FromStr for PipeType {
     }
 }
 
+impl TryFrom<char> for PipeType {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        c.to_string().parse()
+    }
+}
+
 impl Display for PipeType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
@@ -49,6 +57,18 @@ impl Display for PipeType {
 }
 
 impl PipeType {
+    /// The direction a pipe is left through when it is entered from `from`, if it is
+    /// connected that way at all.
+    pub fn exit(&self, from: &Direction) -> Option<Direction> {
+        if !self.is_connected_to(from) {
+            return None;
+        }
+
+        Direction::ALL
+            .into_iter()
+            .find(|d| d != from && self.is_connected_to(d))
+    }
+
     pub fn is_connected_to(&self, direction: &Direction) -> bool {
         match self {
             PipeType::NS => matches!(direction, Direction::North | Direction::South),
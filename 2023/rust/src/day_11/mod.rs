@@ -2,24 +2,63 @@ mod universe;
 
 use universe::Universe;
 
-pub fn part_1(input: &str) -> usize {
-    let mut universe = Universe::new(input);
+use crate::error::AocError;
+use crate::solution::{Answer, Solution};
+
+pub fn part_1(input: &str) -> Result<usize, AocError> {
+    let mut universe = Universe::new(input)?;
     universe.expand(2);
 
-    universe
+    Ok(universe
         .get_distances_between_galaxies()
         .iter()
-        .sum::<usize>()
+        .sum::<usize>())
 }
 
-pub fn part_2(input: &str) -> usize {
-    let mut universe = Universe::new(input);
+pub fn part_2(input: &str) -> Result<usize, AocError> {
+    let mut universe = Universe::new(input)?;
     universe.expand(1_000_000);
 
-    universe
+    Ok(universe
         .get_distances_between_galaxies()
         .iter()
-        .sum::<usize>()
+        .sum::<usize>())
+}
+
+pub struct Day11 {
+    universe: Universe,
+}
+
+impl Solution for Day11 {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Day11 {
+            universe: Universe::new(input)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        let mut universe = self.universe.clone();
+        universe.expand(2);
+
+        Ok(Box::new(
+            universe
+                .get_distances_between_galaxies()
+                .iter()
+                .sum::<usize>(),
+        ))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        let mut universe = self.universe.clone();
+        universe.expand(1_000_000);
+
+        Ok(Box::new(
+            universe
+                .get_distances_between_galaxies()
+                .iter()
+                .sum::<usize>(),
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -27,20 +66,9 @@ mod tests {
     use super::*;
 
     const TEST_INPUT_1: &str = include_str!("./test-input-1.txt");
-    const COMPLETE_INPUT: &str = include_str!("./complete-input.txt");
 
     #[test]
     fn test_part_1_with_test_input() {
-        assert_eq!(part_1(TEST_INPUT_1), 374)
-    }
-
-    #[test]
-    fn test_part_1_with_complete_input() {
-        assert_eq!(part_1(COMPLETE_INPUT), 9795148)
-    }
-
-    #[test]
-    fn test_part_2_with_complete_input() {
-        assert_eq!(part_2(COMPLETE_INPUT), 650672493820)
+        assert_eq!(part_1(TEST_INPUT_1), Ok(374))
     }
 }
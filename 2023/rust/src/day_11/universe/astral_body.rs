@@ -1,5 +1,17 @@
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum AstralBody {
     Void,
     Galaxy,
 }
+
+impl TryFrom<char> for AstralBody {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(AstralBody::Void),
+            '#' => Ok(AstralBody::Galaxy),
+            c => Err(c),
+        }
+    }
+}
@@ -1,30 +1,26 @@
 mod astral_body;
 
-use std::collections::HashMap;
+use crate::error::AocError;
+use crate::grid::Grid;
 
 use self::astral_body::AstralBody;
-use self::astral_body::AstralBody::{Galaxy, Void};
+use self::astral_body::AstralBody::Galaxy;
 
-#[derive(Debug)]
+/// The observed image of the universe. Expansion is not applied to the grid, which would
+/// not fit in memory for large rates; instead positions are mapped between the observed and
+/// the expanded universe on demand.
+#[derive(Debug, Clone)]
 pub struct Universe {
-    astral_bodies: HashMap<(usize, usize), AstralBody>,
+    astral_bodies: Grid<AstralBody>,
+    expansion_rate: usize,
 }
 
 impl Universe {
-    pub fn new(input: &str) -> Self {
-        let mut astral_bodies = HashMap::new();
-        let lines = input.lines();
-        for (row, line) in lines.enumerate() {
-            for (col, c) in line.char_indices() {
-                match c {
-                    '.' => astral_bodies.insert((col, row), Void),
-                    '#' => astral_bodies.insert((col, row), Galaxy),
-                    _ => panic!("Invalid character in input"),
-                };
-            }
-        }
-
-        Universe { astral_bodies }
+    pub fn new(input: &str) -> Result<Self, AocError> {
+        Ok(Universe {
+            astral_bodies: input.parse()?,
+            expansion_rate: 1,
+        })
     }
 
     pub fn expand(&mut self, expansion_rate: usize) {
@@ -32,32 +28,8 @@ impl Universe {
             0 | 1 => 2,
             rate => rate,
         };
-        let (cols, rows) = self.size();
-        let (empty_rows, empty_cols) = (self.get_empty_rows(), self.get_empty_cols());
 
-        let mut astral_bodies = HashMap::new();
-
-        for col in 0..cols {
-            let col_offset =
-                empty_cols.iter().filter(|&&c| c <= col).count() * (expansion_rate - 1);
-            for row in 0..rows {
-                let row_offset =
-                    empty_rows.iter().filter(|&&r| r <= row).count() * (expansion_rate - 1);
-                let new_coords = (col + col_offset, row + row_offset);
-                match self.get_astral_body((col, row)) {
-                    Some(Galaxy) => astral_bodies.insert(new_coords, Galaxy),
-                    Some(Void) => astral_bodies.insert(new_coords, Void),
-                    None => panic!("No astral body found at {:?}", (col, row)),
-                };
-            }
-        }
-
-        let (expanded_cols, expanded_rows) = (cols + empty_cols.len(), rows + empty_rows.len());
-        for (col, row) in (0..expanded_cols).zip(0..expanded_rows) {
-            astral_bodies.entry((col, row)).or_insert(Void);
-        }
-
-        self.astral_bodies = astral_bodies;
+        self.expansion_rate *= expansion_rate;
     }
 
     pub fn get_distances_between_galaxies(&self) -> Vec<usize> {
@@ -65,10 +37,9 @@ impl Universe {
         let mut distances = vec![];
         for a in 0..galaxies.len() {
             for b in (a + 1)..galaxies.len() {
-                let x_steps = num::abs(galaxies[a].0 as isize - galaxies[b].0 as isize);
-                let y_steps = num::abs(galaxies[a].1 as isize - galaxies[b].1 as isize);
-                let distance = (x_steps + y_steps) as usize;
-                distances.push(distance);
+                let x_steps = galaxies[a].0.abs_diff(galaxies[b].0);
+                let y_steps = galaxies[a].1.abs_diff(galaxies[b].1);
+                distances.push(x_steps + y_steps);
             }
         }
 
@@ -76,73 +47,96 @@ impl Universe {
     }
 
     fn get_galaxy_coordinates(&self) -> Vec<(usize, usize)> {
+        let (empty_rows, empty_cols) = (self.get_empty_rows(), self.get_empty_cols());
+        let growth = self.expansion_rate - 1;
+
         self.astral_bodies
             .iter()
-            .filter_map(|a| if *a.1 == Galaxy { Some(*a.0) } else { None })
+            .filter(|(_, astral_body)| **astral_body == Galaxy)
+            .map(|((col, row), _)| {
+                let col_offset = empty_cols.iter().filter(|&&c| c < col).count() * growth;
+                let row_offset = empty_rows.iter().filter(|&&r| r < row).count() * growth;
+
+                (col + col_offset, row + row_offset)
+            })
             .collect()
     }
 
     fn get_empty_rows(&self) -> Vec<usize> {
-        let mut empty_rows = vec![];
-        let (width, height) = self.size();
-
-        'outer: for col in 0..height {
-            for row in 0..width {
-                if self.get_astral_body((row, col)) == Some(&AstralBody::Galaxy) {
-                    continue 'outer;
-                }
-            }
-            empty_rows.push(col);
-        }
-
-        empty_rows
+        self.astral_bodies
+            .rows()
+            .enumerate()
+            .filter(|(_, row)| !row.contains(&Galaxy))
+            .map(|(row, _)| row)
+            .collect()
     }
 
     fn get_empty_cols(&self) -> Vec<usize> {
-        let mut empty_cols = vec![];
-        let (width, height) = self.size();
-
-        'outer: for col in 0..width {
-            for row in 0..height {
-                if self.get_astral_body((col, row)) == Some(&AstralBody::Galaxy) {
-                    continue 'outer;
-                }
-            }
-            empty_cols.push(col);
-        }
-
-        empty_cols
+        self.astral_bodies
+            .columns()
+            .enumerate()
+            .filter_map(|(i, mut col)| {
+                (!col.any(|astral_body| *astral_body == Galaxy)).then_some(i)
+            })
+            .collect()
     }
+}
+
+/// Views of the expanded universe, only needed to check the expansion.
+#[cfg(test)]
+impl Universe {
+    /// The astral body at `coords` of the expanded universe.
+    fn get_astral_body(&self, (col, row): (usize, usize)) -> Option<&AstralBody> {
+        let (cols, rows) = self.astral_bodies.size();
+        let col = self.to_observed(col, cols, &self.get_empty_cols())?;
+        let row = self.to_observed(row, rows, &self.get_empty_rows())?;
 
-    fn get_astral_body(&self, coords: (usize, usize)) -> Option<&AstralBody> {
-        self.astral_bodies.get(&coords)
+        self.astral_bodies.get((col, row))
     }
 
-    fn size(&self) -> (usize, usize) {
-        let mut max_x = 0;
-        let mut max_y = 0;
-        for (x, y) in self.astral_bodies.keys() {
-            if *x > max_x {
-                max_x = *x;
-            }
-            if *y > max_y {
-                max_y = *y;
+    /// Maps an expanded coordinate back to the observed row or column it comes from.
+    fn to_observed(&self, expanded: usize, observed_len: usize, empty: &[usize]) -> Option<usize> {
+        let mut start = 0;
+        for observed in 0..observed_len {
+            let width = if empty.contains(&observed) {
+                self.expansion_rate
+            } else {
+                1
+            };
+
+            if expanded < start + width {
+                return Some(observed);
             }
+
+            start += width;
         }
 
-        (max_x + 1, max_y + 1)
+        None
+    }
+
+    fn size(&self) -> (usize, usize) {
+        let (cols, rows) = self.astral_bodies.size();
+        let growth = self.expansion_rate - 1;
+
+        (
+            cols + self.get_empty_cols().len() * growth,
+            rows + self.get_empty_rows().len() * growth,
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
+    use super::astral_body::AstralBody::Void;
     use super::*;
 
     const TEST_INPUT_1: &str = include_str!("../test-input-1.txt");
 
     #[test]
     fn test_parse_input() {
-        let universe = Universe::new(TEST_INPUT_1);
+        let universe = Universe::new(TEST_INPUT_1).unwrap();
         assert_eq!(universe.get_astral_body((0, 0)), Some(&Void));
         assert_eq!(universe.get_astral_body((3, 0)), Some(&Galaxy));
         assert_eq!(universe.get_astral_body((0, 2)), Some(&Galaxy));
@@ -151,19 +145,19 @@ mod tests {
 
     #[test]
     fn test_find_empty_rows() {
-        let universe = Universe::new(TEST_INPUT_1);
+        let universe = Universe::new(TEST_INPUT_1).unwrap();
         assert_eq!(universe.get_empty_rows(), vec![3, 7]);
     }
 
     #[test]
     fn test_find_empty_cols() {
-        let universe = Universe::new(TEST_INPUT_1);
+        let universe = Universe::new(TEST_INPUT_1).unwrap();
         assert_eq!(universe.get_empty_cols(), vec![2, 5, 8]);
     }
 
     #[test]
     fn test_expand() {
-        let mut universe = Universe::new(TEST_INPUT_1);
+        let mut universe = Universe::new(TEST_INPUT_1).unwrap();
         universe.expand(2);
         assert_eq!(universe.size(), (13, 12));
         assert_eq!(universe.get_astral_body((0, 0)), Some(&Void));
@@ -173,7 +167,7 @@ mod tests {
         assert_eq!(universe.get_astral_body((0, 9)), Some(&Void));
         assert_eq!(universe.get_astral_body((0, 11)), Some(&Galaxy));
 
-        let mut universe = Universe::new(TEST_INPUT_1);
+        let mut universe = Universe::new(TEST_INPUT_1).unwrap();
         universe.expand(10);
         assert_eq!(universe.size(), (37, 28));
         assert_eq!(universe.get_astral_body((0, 0)), Some(&Void));
@@ -182,7 +176,7 @@ mod tests {
 
     #[test]
     fn test_get_distances() {
-        let mut universe = Universe::new(TEST_INPUT_1);
+        let mut universe = Universe::new(TEST_INPUT_1).unwrap();
         universe.expand(2);
         assert_eq!(
             universe
@@ -192,7 +186,7 @@ mod tests {
             374
         );
 
-        let mut universe = Universe::new(TEST_INPUT_1);
+        let mut universe = Universe::new(TEST_INPUT_1).unwrap();
         universe.expand(10);
         assert_eq!(
             universe
@@ -202,7 +196,7 @@ mod tests {
             1030
         );
 
-        let mut universe = Universe::new(TEST_INPUT_1);
+        let mut universe = Universe::new(TEST_INPUT_1).unwrap();
         universe.expand(100);
         assert_eq!(
             universe
@@ -212,4 +206,52 @@ mod tests {
             8410
         );
     }
+
+    fn observed_image() -> impl Strategy<Value = Vec<String>> {
+        (1..8usize, 1..8usize).prop_flat_map(|(cols, rows)| {
+            let row = prop::collection::vec(prop::bool::weighted(0.2), cols)
+                .prop_map(|row| row.into_iter().map(|g| if g { '#' } else { '.' }).collect());
+
+            prop::collection::vec(row, rows)
+        })
+    }
+
+    /// The image of the expanded universe, every empty row and column written `rate` times.
+    fn expand_literally(image: &[String], rate: usize) -> String {
+        let is_empty_col = |col: usize| image.iter().all(|row| row.as_bytes()[col] == b'.');
+
+        image
+            .iter()
+            .flat_map(|row| {
+                let expanded = row
+                    .chars()
+                    .enumerate()
+                    .flat_map(|(col, c)| {
+                        std::iter::repeat_n(c, if is_empty_col(col) { rate } else { 1 })
+                    })
+                    .collect::<String>();
+                let times = if row.contains('#') { 1 } else { rate };
+
+                std::iter::repeat_n(expanded, times)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    proptest! {
+        /// Mapping positions into the expanded universe measures the same distances as
+        /// inserting the empty rows and columns into the image.
+        #[test]
+        fn test_expand_matches_inserting_rows(image in observed_image(), rate in 2..6usize) {
+            let mut universe = Universe::new(&image.join("\n")).unwrap();
+            universe.expand(rate);
+            let expanded = Universe::new(&expand_literally(&image, rate)).unwrap();
+
+            prop_assert_eq!(universe.size(), expanded.size());
+            prop_assert_eq!(
+                universe.get_distances_between_galaxies(),
+                expanded.get_distances_between_galaxies()
+            );
+        }
+    }
 }
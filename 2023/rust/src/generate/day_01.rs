@@ -0,0 +1,39 @@
+use std::io::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+const SPELLED_DIGITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// `size` calibration lines of letters, spelled out digits and digits, with at least one
+/// digit in every line so both parts can read it.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size.max(1) {
+        writeln!(out, "{}", calibration_line(rng))?;
+    }
+
+    Ok(())
+}
+
+fn calibration_line(rng: &mut Rng) -> String {
+    let tokens = rng.gen_range(1..=8);
+    let digit_at = rng.gen_range(0..tokens);
+    let mut line = String::new();
+
+    for i in 0..tokens {
+        if i == digit_at || rng.gen_bool(0.2) {
+            line.push(char::from(b'1' + rng.gen_range(0..9)));
+        } else if rng.gen_bool(0.3) {
+            line.push_str(SPELLED_DIGITS[rng.gen_range(0..9)]);
+        } else {
+            for _ in 0..rng.gen_range(1..=4) {
+                line.push(char::from(rng.gen_range(b'a'..=b'z')));
+            }
+        }
+    }
+
+    line
+}
@@ -0,0 +1,30 @@
+use std::io::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// `size` games of one to six sets, each showing some of the colours at most once.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for index in 1..=size.max(1) {
+        let sets = (0..rng.gen_range(1..=6))
+            .map(|_| random_set(rng))
+            .collect::<Vec<_>>();
+
+        writeln!(out, "Game {}: {}", index, sets.join("; "))?;
+    }
+
+    Ok(())
+}
+
+fn random_set(rng: &mut Rng) -> String {
+    let mut colours = ["red", "green", "blue"];
+    colours.shuffle(rng);
+
+    let shown = rng.gen_range(1..=colours.len());
+    colours[..shown]
+        .iter()
+        .map(|colour| format!("{} {}", rng.gen_range(1..=20), colour))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,39 @@
+use std::io::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+use crate::grid::Grid;
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+
+/// A `size` by `size` schematic of numbers of up to three digits among dots and symbols.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let side = size.max(1);
+    let mut schematic = Grid::from_fn(side, side, |_| '.');
+
+    for y in 0..side {
+        let mut x = 0;
+        while x < side {
+            if rng.gen_bool(0.15) {
+                let len = rng.gen_range(1..=3).min(side - x);
+                for offset in 0..len {
+                    let first = offset == 0 && len > 1;
+                    let digit = rng.gen_range(if first { 1 } else { 0 }..=9);
+                    schematic[(x + offset, y)] = char::from(b'0' + digit);
+                }
+
+                // leave a dot after the number so it does not run into the next one
+                x += len + 1;
+            } else {
+                if rng.gen_bool(0.1) {
+                    schematic[(x, y)] = *SYMBOLS.choose(rng).unwrap();
+                }
+
+                x += 1;
+            }
+        }
+    }
+
+    write!(out, "{}", schematic)
+}
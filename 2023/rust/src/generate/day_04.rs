@@ -0,0 +1,66 @@
+use std::io::{self, Write};
+
+use rand::{
+    seq::{index, SliceRandom},
+    Rng as _,
+};
+
+use super::Rng;
+
+/// The highest number on a card.
+const HIGHEST_NUMBER: usize = 99;
+
+/// `size` scratchcards, all with either 5 winning and 8 received numbers or 10 and 25, the
+/// two shapes of the puzzle. No card wins copies of cards past the end, and few cards match
+/// many numbers so the count of scratchcards stays small however many cards there are.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let cards = size.max(1);
+    let (winning, received) = if rng.gen_bool(0.5) { (5, 8) } else { (10, 25) };
+    let id_width = cards.to_string().len();
+
+    for id in 1..=cards {
+        let matching = random_matching(rng, winning.min(cards - id));
+        let numbers = index::sample(rng, HIGHEST_NUMBER, winning + received - matching)
+            .into_iter()
+            .map(|i| i + 1)
+            .collect::<Vec<_>>();
+
+        let winning_numbers = &numbers[..winning];
+        let mut received_numbers = numbers[..matching]
+            .iter()
+            .chain(&numbers[winning..])
+            .copied()
+            .collect::<Vec<_>>();
+        received_numbers.shuffle(rng);
+
+        writeln!(
+            out,
+            "Card {:>w$}: {} | {}",
+            id,
+            format_numbers(winning_numbers),
+            format_numbers(&received_numbers),
+            w = id_width
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Every further match is less likely than the one before, so on average a card wins fewer
+/// than one copy and the copies of copies die out.
+fn random_matching(rng: &mut Rng, most: usize) -> usize {
+    let mut matching = 0;
+    while matching < most && rng.gen_bool(0.4) {
+        matching += 1;
+    }
+
+    matching
+}
+
+fn format_numbers(numbers: &[usize]) -> String {
+    numbers
+        .iter()
+        .map(|n| format!("{:>2}", n))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
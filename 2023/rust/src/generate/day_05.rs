@@ -0,0 +1,64 @@
+use std::io::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// Values and lengths stay below this, so no sum of them overflows.
+const MAX_VALUE: u64 = 1 << 32;
+
+/// An almanac of `size` pairs of seeds and maps of up to `size` ranges each, where the
+/// source ranges of a map do not overlap.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let size = size.max(1);
+
+    let seeds = (0..size)
+        .map(|_| {
+            let start = rng.gen_range(0..MAX_VALUE);
+            let length = rng.gen_range(1..=MAX_VALUE >> 8);
+            format!("{} {}", start, length)
+        })
+        .collect::<Vec<_>>();
+    writeln!(out, "seeds: {}", seeds.join(" "))?;
+
+    for name in MAPS {
+        writeln!(out)?;
+        writeln!(out, "{} map:", name)?;
+
+        let count = rng.gen_range(1..=size.min(1 << 20));
+        let mut ranges = random_ranges(rng, count);
+        ranges.shuffle(rng);
+        for (destination, source, length) in ranges {
+            writeln!(out, "{} {} {}", destination, source, length)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `count` ranges as `(destination, source, length)`, with disjoint sources.
+fn random_ranges(rng: &mut Rng, count: usize) -> Vec<(u64, u64, u64)> {
+    let step = MAX_VALUE / count as u64;
+    let mut ranges = Vec::with_capacity(count);
+
+    let mut cursor = 0;
+    for _ in 0..count {
+        let source = cursor + rng.gen_range(0..=step / 2);
+        let length = rng.gen_range(1..=step / 2);
+        ranges.push((rng.gen_range(0..MAX_VALUE), source, length));
+
+        cursor = source + length;
+    }
+
+    ranges
+}
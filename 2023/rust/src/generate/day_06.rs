@@ -0,0 +1,77 @@
+use std::io::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// The most digits of the time of all races read as one number, which keeps the record
+/// distance of that race within a `u64` and the brute force of part 2 around a second.
+const MAX_DIGITS: usize = 9;
+
+/// Up to four races whose times have `size` digits in total, so part 2 grows with the size.
+/// Every race can be won, also when the races are read as a single one.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let digits = size.clamp(1, MAX_DIGITS);
+    let races = rng.gen_range(1..=digits.min(4));
+
+    let mut race_digits = vec![1; races];
+    for _ in races..digits {
+        race_digits[rng.gen_range(0..races)] += 1;
+    }
+
+    let times = race_digits
+        .iter()
+        .map(|&digits| random_time(rng, digits))
+        .collect::<Vec<_>>();
+    let total_time = concat(&times);
+
+    let distances = loop {
+        let distances = times
+            .iter()
+            .map(|&time| rng.gen_range(0..best_distance(time)))
+            .collect::<Vec<_>>();
+
+        if concat(&distances) < best_distance(total_time) {
+            break distances;
+        }
+    };
+
+    let widths = times
+        .iter()
+        .zip(&distances)
+        .map(|(time, distance)| time.to_string().len().max(distance.to_string().len()))
+        .collect::<Vec<_>>();
+
+    write!(out, "Time:    ")?;
+    for (time, width) in times.iter().zip(&widths) {
+        write!(out, "  {:>w$}", time, w = width)?;
+    }
+    writeln!(out)?;
+
+    write!(out, "Distance:")?;
+    for (distance, width) in distances.iter().zip(&widths) {
+        write!(out, "  {:>w$}", distance, w = width)?;
+    }
+    writeln!(out)
+}
+
+/// A time of `digits` digits that is long enough to go any distance at all.
+fn random_time(rng: &mut Rng, digits: usize) -> u64 {
+    let lowest = 10u64.pow(digits as u32 - 1).max(2);
+
+    rng.gen_range(lowest..10u64.pow(digits as u32))
+}
+
+/// The distance of holding the button for half the time, which is as far as a boat goes.
+fn best_distance(time: u64) -> u64 {
+    (time / 2) * (time - time / 2)
+}
+
+fn concat(numbers: &[u64]) -> u64 {
+    numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<String>()
+        .parse()
+        .unwrap()
+}
@@ -0,0 +1,86 @@
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+const LABELS: [char; 13] = [
+    'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+];
+
+/// How many times labels repeat in every type of hand, from five of a kind to high card.
+const SHAPES: [&[usize]; 7] = [
+    &[5],
+    &[4, 1],
+    &[3, 2],
+    &[3, 1, 1],
+    &[2, 2, 1],
+    &[2, 1, 1, 1],
+    &[1, 1, 1, 1, 1],
+];
+
+/// There are 13 labels for each of the 5 cards.
+const ALL_HANDS: usize = 13 * 13 * 13 * 13 * 13;
+
+/// `size` different hands with their bids, at most as many as there are hands. The types of
+/// hands are about as common as each other, where a hand dealt at random would rarely be
+/// better than a pair.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let size = size.clamp(1, ALL_HANDS);
+
+    for hand in random_hands(rng, size) {
+        writeln!(out, "{} {}", hand, rng.gen_range(1..=1000))?;
+    }
+
+    Ok(())
+}
+
+fn random_hands(rng: &mut Rng, size: usize) -> Vec<String> {
+    // most hands are high cards or pairs, so drawing types evenly would take long to find
+    // the last ones
+    if size > ALL_HANDS / 2 {
+        let mut hands = (0..ALL_HANDS).map(nth_hand).collect::<Vec<_>>();
+        hands.shuffle(rng);
+        hands.truncate(size);
+
+        return hands;
+    }
+
+    let mut seen = HashSet::new();
+    let mut hands = Vec::with_capacity(size);
+    while hands.len() < size {
+        let hand = random_hand(rng);
+        if seen.insert(hand.clone()) {
+            hands.push(hand);
+        }
+    }
+
+    hands
+}
+
+fn random_hand(rng: &mut Rng) -> String {
+    let shape = SHAPES.choose(rng).unwrap();
+    let labels = LABELS.choose_multiple(rng, shape.len());
+
+    let mut cards = shape
+        .iter()
+        .zip(labels)
+        .flat_map(|(&count, &label)| std::iter::repeat_n(label, count))
+        .collect::<Vec<_>>();
+    cards.shuffle(rng);
+
+    cards.into_iter().collect()
+}
+
+fn nth_hand(mut n: usize) -> String {
+    (0..5)
+        .map(|_| {
+            let label = LABELS[n % LABELS.len()];
+            n /= LABELS.len();
+            label
+        })
+        .collect()
+}
@@ -0,0 +1,134 @@
+use std::io::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+
+/// The number of times every ghost goes through the instructions before it is back at its
+/// end node. Different primes keep their least common multiple far from any one of them.
+const LAPS: [usize; 10] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29];
+
+/// Letters that end neither a start node nor an end node.
+const MIDDLE_LETTERS: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY";
+
+/// A network of about `size` nodes where every ghost, starting with the one at `AAA`, walks
+/// a ring of its own. A ring takes a ghost from its start node to its end node in a number
+/// of steps that is a multiple of the instructions, and then round to the same end node in
+/// as many steps again, which is what lets part 2 take the least common multiple of the
+/// walks. The side of a node the instructions never take leads anywhere.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let ghosts = rng.gen_range(1..=6);
+    let laps = LAPS
+        .choose_multiple(rng, ghosts)
+        .copied()
+        .collect::<Vec<_>>();
+    let instructions_len = (size / laps.iter().sum::<usize>()).max(1);
+    let goes_left = (0..instructions_len)
+        .map(|_| rng.gen_bool(0.5))
+        .collect::<Vec<_>>();
+
+    let total_nodes = ghosts + instructions_len * laps.iter().sum::<usize>();
+    let mut names = Names::new(total_nodes);
+
+    // every ring is its start node followed by the nodes it goes round, the end node first
+    let rings = laps
+        .iter()
+        .enumerate()
+        .map(|(ghost, laps)| {
+            let mut ring = vec![names.start(ghost), names.end(ghost)];
+            ring.extend((1..laps * instructions_len).map(|_| names.next()));
+            ring
+        })
+        .collect::<Vec<_>>();
+
+    let all_names = rings.iter().flatten().cloned().collect::<Vec<_>>();
+    let mut lines = Vec::with_capacity(total_nodes);
+    let mut connect = |node: &str, next: &str, step: usize| {
+        let other = all_names.choose(rng).unwrap();
+        let (left, right) = if goes_left[step % instructions_len] {
+            (next, other.as_str())
+        } else {
+            (other.as_str(), next)
+        };
+
+        lines.push(format!("{} = ({}, {})", node, left, right));
+    };
+
+    for ring in rings.iter() {
+        let round = ring.len() - 1;
+
+        for step in 0..round {
+            let node = if step == 0 { &ring[0] } else { &ring[step + 1] };
+            connect(node, &ring[(step + 1) % round + 1], step);
+        }
+
+        // the end node carries on as the first node of the ring
+        connect(&ring[1], &ring[2], 0);
+    }
+    lines.shuffle(rng);
+
+    let instructions = goes_left
+        .iter()
+        .map(|&left| if left { 'L' } else { 'R' })
+        .collect::<String>();
+    writeln!(out, "{}", instructions)?;
+    writeln!(out)?;
+    for line in lines {
+        writeln!(out, "{}", line)?;
+    }
+
+    Ok(())
+}
+
+/// Hands out node names of the same length, long enough for all the nodes.
+struct Names {
+    len: usize,
+    next: usize,
+}
+
+impl Names {
+    fn new(total: usize) -> Self {
+        let mut len = 3;
+        while 26usize.pow(len as u32 - 1) * MIDDLE_LETTERS.len() < total {
+            len += 1;
+        }
+
+        Names { len, next: 0 }
+    }
+
+    /// The start node of a ghost, the first ghost being the one at `AAA`.
+    fn start(&self, ghost: usize) -> String {
+        match ghost {
+            0 => "AAA".to_string(),
+            _ => self.encode(ghost, b'A'),
+        }
+    }
+
+    /// The end node of a ghost, the first ghost ending at `ZZZ`.
+    fn end(&self, ghost: usize) -> String {
+        match ghost {
+            0 => "ZZZ".to_string(),
+            _ => self.encode(ghost, b'Z'),
+        }
+    }
+
+    fn next(&mut self) -> String {
+        let last = MIDDLE_LETTERS[self.next % MIDDLE_LETTERS.len()];
+        let name = self.encode(self.next / MIDDLE_LETTERS.len(), last);
+        self.next += 1;
+
+        name
+    }
+
+    /// Writes `n` in base 26 with letters, followed by `last`.
+    fn encode(&self, mut n: usize, last: u8) -> String {
+        let mut name = vec![b'A'; self.len];
+        name[self.len - 1] = last;
+        for letter in name[..self.len - 1].iter_mut().rev() {
+            *letter = b'A' + (n % 26) as u8;
+            n /= 26;
+        }
+
+        String::from_utf8(name).unwrap()
+    }
+}
@@ -0,0 +1,31 @@
+use std::io::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+
+/// `size` report lines, each the values of a polynomial with small integer coefficients at
+/// consecutive points. A line has at least two values more than the degree of its
+/// polynomial, so its differences always come down to zeros.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size.max(1) {
+        let len = rng.gen_range(2..=21);
+        let degree = rng.gen_range(0..=(len - 2).min(5));
+        let coefficients = (0..=degree)
+            .map(|_| rng.gen_range(-9..=9))
+            .collect::<Vec<i64>>();
+        let first = rng.gen_range(-10..=10);
+
+        let values = (first..first + len as i64)
+            .map(|x| evaluate(&coefficients, x).to_string())
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", values.join(" "))?;
+    }
+
+    Ok(())
+}
+
+/// Evaluates the polynomial with the given coefficients, lowest degree first.
+fn evaluate(coefficients: &[i64], x: i64) -> i64 {
+    coefficients.iter().rev().fold(0, |value, c| value * x + c)
+}
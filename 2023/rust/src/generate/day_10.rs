@@ -0,0 +1,138 @@
+use std::io::{self, Write};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use super::Rng;
+use crate::grid::Grid;
+
+/// The cells of two nodes joined by an edge.
+type Edge = ((usize, usize), (usize, usize));
+
+const NORTH: u8 = 1;
+const EAST: u8 = 2;
+const SOUTH: u8 = 4;
+const WEST: u8 = 8;
+
+/// A maze of about `size` by `size` tiles around a single loop, with stray pipes everywhere
+/// else.
+///
+/// The loop is the outline of a random tree drawn with thick lines: the nodes of the tree
+/// are the cells at even coordinates, the edges the cells between them, and the tiles of the
+/// maze are the corners of the cells. Since the tree has no cycles the drawing has no holes,
+/// and since both ends of every edge are drawn no two cells only touch at a corner, so its
+/// outline goes through every corner at most once. The drawing is scaled up so the loop
+/// also encloses some tiles.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let scale = rng.gen_range(1..=3);
+    let nodes_wide = (size / (2 * scale)).max(1);
+    let nodes_high = (size / (2 * scale)).max(1);
+    let tree = draw_random_tree(rng, nodes_wide, nodes_high);
+    let drawing = Grid::from_fn(tree.width() * scale, tree.height() * scale, |(x, y)| {
+        tree[(x / scale, y / scale)]
+    });
+
+    let mut connections = Grid::from_fn(drawing.width() + 1, drawing.height() + 1, |_| 0u8);
+    for ((x, y), _) in drawing.iter().filter(|(_, filled)| **filled) {
+        let is_empty = |dx: isize, dy: isize| {
+            drawing
+                .step((x, y), (dx, dy))
+                .is_none_or(|neighbour| !drawing[neighbour])
+        };
+
+        if is_empty(0, -1) {
+            connections[(x, y)] |= EAST;
+            connections[(x + 1, y)] |= WEST;
+        }
+        if is_empty(0, 1) {
+            connections[(x, y + 1)] |= EAST;
+            connections[(x + 1, y + 1)] |= WEST;
+        }
+        if is_empty(-1, 0) {
+            connections[(x, y)] |= SOUTH;
+            connections[(x, y + 1)] |= NORTH;
+        }
+        if is_empty(1, 0) {
+            connections[(x + 1, y)] |= SOUTH;
+            connections[(x + 1, y + 1)] |= NORTH;
+        }
+    }
+
+    let mut maze = connections.map(|connection| match *connection {
+        0 => *['|', '-', 'L', 'J', '7', 'F', '.', '.']
+            .choose(rng)
+            .unwrap(),
+        connection => pipe(connection),
+    });
+
+    let on_loop = connections
+        .iter()
+        .filter(|(_, connection)| **connection != 0)
+        .map(|(position, _)| position)
+        .collect::<Vec<_>>();
+    let start = *on_loop.choose(rng).unwrap();
+    maze[start] = 'S';
+
+    // the start pipe takes the shape of whatever connects to it, so nothing else may
+    let around_start = maze.neighbours(start).map(|(p, _)| p).collect::<Vec<_>>();
+    for neighbour in around_start {
+        if connections[neighbour] == 0 {
+            maze[neighbour] = '.';
+        }
+    }
+
+    write!(out, "{}", maze)
+}
+
+fn pipe(connection: u8) -> char {
+    match connection {
+        c if c == NORTH | SOUTH => '|',
+        c if c == EAST | WEST => '-',
+        c if c == NORTH | EAST => 'L',
+        c if c == NORTH | WEST => 'J',
+        c if c == SOUTH | WEST => '7',
+        c if c == SOUTH | EAST => 'F',
+        c => unreachable!("a corner of the outline joins two sides, not {:b}", c),
+    }
+}
+
+/// Draws a random tree over some of the nodes of a grid: node `(x, y)` is the cell
+/// `(2x, 2y)` and the edge between two nodes is the cell between them. The tree grows from a
+/// random node through random edges of its frontier, like Prim's algorithm with random
+/// weights, until it has between half and all the nodes.
+fn draw_random_tree(rng: &mut Rng, nodes_wide: usize, nodes_high: usize) -> Grid<bool> {
+    let mut drawing = Grid::from_fn(2 * nodes_wide - 1, 2 * nodes_high - 1, |_| false);
+    let nodes = nodes_wide * nodes_high;
+    let target = rng.gen_range(nodes.div_ceil(2)..=nodes);
+
+    let root = (
+        2 * rng.gen_range(0..nodes_wide),
+        2 * rng.gen_range(0..nodes_high),
+    );
+    let mut frontier = Vec::new();
+    add_node(&mut drawing, &mut frontier, root);
+
+    let mut added = 1;
+    while added < target && !frontier.is_empty() {
+        let (from, to) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        if drawing[to] {
+            continue;
+        }
+
+        drawing[((from.0 + to.0) / 2, (from.1 + to.1) / 2)] = true;
+        add_node(&mut drawing, &mut frontier, to);
+        added += 1;
+    }
+
+    drawing
+}
+
+/// Draws the node at `cell` and adds the edges to its neighbours to the frontier.
+fn add_node(drawing: &mut Grid<bool>, frontier: &mut Vec<Edge>, cell: (usize, usize)) {
+    drawing[cell] = true;
+
+    for offset in [(0, -2), (2, 0), (0, 2), (-2, 0)] {
+        if let Some(neighbour) = drawing.step(cell, offset) {
+            frontier.push((cell, neighbour));
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use std::io::{self, Write};
+
+use rand::Rng as _;
+
+use super::Rng;
+use crate::grid::Grid;
+
+/// A `size` by `size` image of galaxies, with some rows and columns left empty on purpose
+/// so the universe has somewhere to expand.
+pub fn generate(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let side = size.max(1);
+    let empty_rows = (0..side).map(|_| rng.gen_bool(0.15)).collect::<Vec<_>>();
+    let empty_columns = (0..side).map(|_| rng.gen_bool(0.15)).collect::<Vec<_>>();
+
+    let mut image = Grid::from_fn(side, side, |_| '.');
+    for y in (0..side).filter(|y| !empty_rows[*y]) {
+        for x in (0..side).filter(|x| !empty_columns[*x]) {
+            if rng.gen_bool(0.1) {
+                image[(x, y)] = '#';
+            }
+        }
+    }
+
+    write!(out, "{}", image)
+}
@@ -0,0 +1,108 @@
+//! Random but valid puzzle inputs of every day, to stress the parsers and solvers with more
+//! than the committed inputs. Every generator takes a seed, so a failing input can be
+//! generated again, and a size, so inputs can be as tiny or as huge as needed. What the size
+//! counts is up to each day.
+
+use std::io::Write;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::error::AocError;
+
+mod day_01;
+mod day_02;
+mod day_03;
+mod day_04;
+mod day_05;
+mod day_06;
+mod day_07;
+mod day_08;
+mod day_09;
+mod day_10;
+mod day_11;
+
+/// The random number generator every generator draws from. ChaCha8 gives the same numbers
+/// for the same seed on every platform and version, which `StdRng` does not promise.
+pub type Rng = ChaCha8Rng;
+
+/// Writes a random input of `size` to the writer.
+pub type Generator = fn(&mut Rng, usize, &mut dyn Write) -> std::io::Result<()>;
+
+pub fn get_generator(day: u8) -> Option<Generator> {
+    match day {
+        1 => Some(day_01::generate),
+        2 => Some(day_02::generate),
+        3 => Some(day_03::generate),
+        4 => Some(day_04::generate),
+        5 => Some(day_05::generate),
+        6 => Some(day_06::generate),
+        7 => Some(day_07::generate),
+        8 => Some(day_08::generate),
+        9 => Some(day_09::generate),
+        10 => Some(day_10::generate),
+        11 => Some(day_11::generate),
+        _ => None,
+    }
+}
+
+/// Writes the input of `day` generated from `seed` to `out`, without holding it in memory
+/// for the days whose inputs are a list of lines.
+pub fn write_input(day: u8, seed: u64, size: usize, out: &mut dyn Write) -> Result<(), AocError> {
+    let generator = get_generator(day)
+        .ok_or_else(|| AocError::Invalid(format!("there is no generator for day {}", day)))?;
+
+    generator(&mut Rng::seed_from_u64(seed), size, out)?;
+
+    Ok(())
+}
+
+pub fn generate(day: u8, seed: u64, size: usize) -> Result<String, AocError> {
+    let mut input = Vec::new();
+    write_input(day, seed, size, &mut input)?;
+
+    Ok(String::from_utf8(input).expect("generators only write ASCII"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_days, get_solver};
+
+    #[test]
+    fn test_every_day_has_a_generator() {
+        assert!(get_days()
+            .into_iter()
+            .all(|day| get_generator(day).is_some()));
+    }
+
+    #[test]
+    fn test_generated_inputs_are_solvable() {
+        for day in get_days() {
+            let solver = get_solver(day).unwrap();
+
+            for size in [0, 1, 2, 3, 5] {
+                for seed in 0..10 {
+                    let input = generate(day, seed, size).unwrap();
+                    let solution = solver(&input).unwrap_or_else(|e| {
+                        panic!("day {} seed {} size {}: {}", day, seed, size, e)
+                    });
+
+                    for answer in [solution.part_one(), solution.part_two()] {
+                        if let Err(e) = answer {
+                            panic!("day {} seed {} size {}: {}", day, seed, size, e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generated_inputs_are_reproducible() {
+        for day in get_days() {
+            assert_eq!(generate(day, 7, 10), generate(day, 7, 10));
+            assert_ne!(generate(day, 7, 10), generate(day, 8, 10));
+        }
+    }
+}
@@ -0,0 +1,367 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use nom::error::ErrorKind;
+
+use crate::error::AocError;
+
+/// Offsets of the 4 orthogonal neighbours, clockwise from north.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the 8 surrounding neighbours, clockwise from north.
+const SURROUNDING: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid of cells stored row by row. Positions are `(x, y)` pairs, where `x`
+/// is the column and `y` the row, both starting at the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self, AocError> {
+        if cells.len() != width * height {
+            return Err(AocError::Invalid(format!(
+                "a {}x{} grid needs {} cells but got {}",
+                width,
+                height,
+                width * height,
+                cells.len()
+            )));
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut((usize, usize)) -> T,
+    {
+        let cells = (0..width * height)
+            .map(|i| f((i % width, i / width)))
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from `lines`, turning every character into a cell with `parse_cell`.
+    /// Trailing empty lines are ignored, but every other line must be as wide as the first.
+    pub fn parse_lines<'a, I, F>(lines: I, mut parse_cell: F) -> Result<Self, AocError>
+    where
+        I: IntoIterator<Item = &'a str>,
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut lines = lines.into_iter().collect::<Vec<_>>();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        let width = lines.first().map(|line| line.chars().count()).unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in lines.iter().enumerate() {
+            let mut line_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                let cell = parse_cell(c).ok_or(AocError::Parse {
+                    line: y + 1,
+                    column: x + 1,
+                    kind: ErrorKind::Char,
+                })?;
+
+                cells.push(cell);
+                line_width += 1;
+            }
+
+            if line_width != width {
+                return Err(AocError::Invalid(format!(
+                    "line {} is {} wide but the grid is {} wide",
+                    y + 1,
+                    line_width,
+                    width
+                )));
+            }
+        }
+
+        Grid::new(width, lines.len(), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The `(width, height)` of the grid.
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        if !self.contains(position) {
+            return None;
+        }
+
+        self.cells.get(self.index_of(position))
+    }
+
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        if !self.contains(position) {
+            return None;
+        }
+
+        let index = self.index_of(position);
+        self.cells.get_mut(index)
+    }
+
+    /// Moves from `position` by `(dx, dy)`, as long as it stays inside the grid.
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+
+        self.contains(position).then_some(position)
+    }
+
+    /// Every cell together with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    pub fn position<P>(&self, mut predicate: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y >= self.height {
+            return None;
+        }
+
+        Some(&self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        if x >= self.width {
+            return None;
+        }
+
+        Some(self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    /// The north, east, south and west neighbours of `position` that are inside the grid.
+    pub fn neighbours(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours_at(position, &ORTHOGONAL)
+    }
+
+    /// The up to 8 neighbours of `position`, diagonals included, that are inside the grid.
+    pub fn surrounding(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours_at(position, &SURROUNDING)
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    fn neighbours_at<'a>(
+        &'a self,
+        position: (usize, usize),
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        offsets
+            .iter()
+            .filter_map(move |offset| self.step(position, *offset))
+            .map(|neighbour| (neighbour, &self[neighbour]))
+    }
+
+    fn index_of(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &Self::Output {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside of a {}x{} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut Self::Output {
+        let (width, height) = self.size();
+
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside of a {}x{} grid",
+                position, width, height
+            )
+        })
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_lines(s.lines(), |c| T::try_from(c).ok())
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_from_str() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+
+        assert_eq!(grid.size(), (3, 2));
+        assert_eq!(grid.get((0, 0)), Some(&'a'));
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.to_string(), INPUT);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let digits = Grid::parse_lines("12\n3x".lines(), |c| c.to_digit(10));
+        assert_eq!(
+            digits,
+            Err(AocError::Parse {
+                line: 2,
+                column: 2,
+                kind: ErrorKind::Char
+            })
+        );
+
+        let ragged = "abc\nde".parse::<Grid<char>>();
+        assert_eq!(
+            ragged,
+            Err(AocError::Invalid(
+                "line 2 is 2 wide but the grid is 3 wide".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(
+            grid.column(1).unwrap().collect::<String>(),
+            "be".to_string()
+        );
+        assert!(grid.column(3).is_none());
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+
+        let neighbours = grid.neighbours((0, 0)).map(|(_, c)| *c).collect::<String>();
+        assert_eq!(neighbours, "bd");
+
+        let neighbours = grid.neighbours((1, 1)).map(|(_, c)| *c).collect::<String>();
+        assert_eq!(neighbours, "bfd");
+
+        let surrounding = grid
+            .surrounding((1, 0))
+            .map(|(_, c)| *c)
+            .collect::<String>();
+        assert_eq!(surrounding, "cfeda");
+    }
+
+    #[test]
+    fn test_step() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+
+        assert_eq!(grid.step((0, 0), (1, 1)), Some((1, 1)));
+        assert_eq!(grid.step((0, 0), (-1, 0)), None);
+        assert_eq!(grid.step((2, 1), (1, 0)), None);
+    }
+}
@@ -9,3 +9,81 @@ pub mod day_08;
 pub mod day_09;
 pub mod day_10;
 pub mod day_11;
+pub mod generate;
+pub mod grid;
+
+pub use aoc_common::{error, input, solution};
+
+use solution::{solver, streamer, Solver, Streamer};
+
+/// The year of the calendar the days of this crate belong to.
+pub const YEAR: u16 = 2023;
+
+pub fn get_solver(day: u8) -> Option<Solver> {
+    match day {
+        1 => Some(solver::<day_01::Day01>),
+        2 => Some(solver::<day_02::Day02>),
+        3 => Some(solver::<day_03::Day03>),
+        4 => Some(solver::<day_04::Day04>),
+        5 => Some(solver::<day_05::Day05>),
+        6 => Some(solver::<day_06::Day06>),
+        7 => Some(solver::<day_07::Day07>),
+        8 => Some(solver::<day_08::Day08>),
+        9 => Some(solver::<day_09::Day09>),
+        10 => Some(solver::<day_10::Day10>),
+        11 => Some(solver::<day_11::Day11>),
+        _ => None,
+    }
+}
+
+pub fn get_days() -> Vec<u8> {
+    (1..=25).filter(|day| get_solver(*day).is_some()).collect()
+}
+
+/// The days that can also be solved one line at a time, see [`solution::Streaming`].
+pub fn get_streamer(day: u8) -> Option<Streamer> {
+    match day {
+        1 => Some(streamer::<day_01::Day01>),
+        2 => Some(streamer::<day_02::Day02>),
+        4 => Some(streamer::<day_04::Day04>),
+        9 => Some(streamer::<day_09::Day09>),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_days() {
+        assert_eq!(get_days(), (1..=11).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_get_solver() {
+        let input = include_str!("day_07/test_input.txt");
+        let solution = get_solver(7).unwrap()(input).unwrap();
+
+        assert_eq!(solution.part_one().unwrap().to_string(), "6440");
+        assert_eq!(solution.part_two().unwrap().to_string(), "5905");
+        assert!(get_solver(12).is_none());
+    }
+
+    #[test]
+    fn test_get_streamer() {
+        let input = include_str!("day_09/test_input.txt");
+        let streamer = get_streamer(9).unwrap();
+
+        assert_eq!(
+            streamer(&mut input.as_bytes(), 1).unwrap().0.to_string(),
+            "114"
+        );
+        assert_eq!(
+            streamer(&mut input.as_bytes(), 2).unwrap().0.to_string(),
+            "2"
+        );
+        assert!(streamer(&mut input.as_bytes(), 3).is_err());
+        assert!(get_streamer(3).is_none());
+    }
+}
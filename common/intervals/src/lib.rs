@@ -0,0 +1,325 @@
+//! Sets of numbers kept as the ranges they make up, for puzzles about ranges too large to
+//! hold every number of.
+
+use std::ops::{Add, Range, Sub};
+
+/// A set of numbers kept as sorted, half-open ranges that neither overlap nor touch, so
+/// there is a single way of writing every set and the operations between two sets only
+/// walk through their ranges once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ranges of the set, in order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn into_ranges(self) -> Vec<Range<T>> {
+        self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest number of the set.
+    pub fn first(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// Adds the numbers of `range`, joining it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        // the ranges from `first` up to `last` overlap or touch `range`
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let joined = match first < last {
+            true => {
+                self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+            }
+            false => range,
+        };
+
+        self.ranges.splice(first..last, [joined]);
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let i = self.ranges.partition_point(|range| range.end <= *value);
+
+        self.ranges
+            .get(i)
+            .is_some_and(|range| range.contains(value))
+    }
+
+    pub fn is_subset(&self, other: &IntervalSet<T>) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_superset(&self, other: &IntervalSet<T>) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Whether the sets share any number.
+    pub fn overlaps(&self, other: &IntervalSet<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> Self {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let (mut i, mut j) = (0, 0);
+        let mut ranges: Vec<Range<T>> = vec![];
+
+        // the ranges of both sets are taken by their starts, joining each with the last one
+        while i < a.len() || j < b.len() {
+            let range = match j == b.len() || (i < a.len() && a[i].start <= b[j].start) {
+                true => {
+                    i += 1;
+                    a[i - 1].clone()
+                }
+                false => {
+                    j += 1;
+                    b[j - 1].clone()
+                }
+            };
+
+            match ranges.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => ranges.push(range),
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> Self {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+
+        while i < a.len() && j < b.len() {
+            let start = a[i].start.max(b[j].start);
+            let end = a[i].end.min(b[j].end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            match a[i].end < b[j].end {
+                true => i += 1,
+                false => j += 1,
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    /// The numbers of the set that are not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> Self {
+        let removed = &other.ranges;
+        let mut j = 0;
+        let mut ranges = vec![];
+
+        for range in &self.ranges {
+            // the ranges removed before this one cannot reach the next ones either
+            while j < removed.len() && removed[j].end <= range.start {
+                j += 1;
+            }
+
+            let mut start = range.start;
+            for r in removed[j..].iter().take_while(|r| r.start < range.end) {
+                if start < r.start {
+                    ranges.push(start..r.start);
+                }
+                start = start.max(r.end);
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> IntervalSet<T> {
+    /// The numbers of the set that are in `source`, moved along with it so that
+    /// `source.start` becomes `destination`.
+    pub fn shift(&self, source: Range<T>, destination: T) -> Self {
+        let ranges = self
+            .intersection(&IntervalSet::from(source.clone()))
+            .ranges
+            .into_iter()
+            .map(|range| {
+                range.start - source.start + destination..range.end - source.start + destination
+            })
+            .collect();
+
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        std::iter::once(range).collect()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        IntervalSet {
+            ranges: coalesce(iter.into_iter().collect()),
+        }
+    }
+}
+
+/// Sorts `ranges` and joins those that overlap or touch, leaving the empty ones out.
+fn coalesce<T: Copy + Ord>(mut ranges: Vec<Range<T>>) -> Vec<Range<T>> {
+    ranges.retain(|range| range.start < range.end);
+    ranges.sort_by_key(|range| range.start);
+
+    let mut coalesced: Vec<Range<T>> = vec![];
+    for range in ranges {
+        match coalesced.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => coalesced.push(range),
+        }
+    }
+
+    coalesced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<u32>]) -> IntervalSet<u32> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_coalescing() {
+        assert_eq!(
+            set(&[5..8, 0..2, 2..3, 7..10, 4..4]).ranges(),
+            [0..3, 5..10]
+        );
+
+        let mut intervals = set(&[0..2, 6..8]);
+        intervals.insert(2..6);
+        assert_eq!(intervals, IntervalSet::from(0..8));
+        assert_eq!(intervals.first(), Some(0));
+        assert!(IntervalSet::<u32>::new().is_empty());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut intervals = set(&[2..4, 6..8, 10..12, 20..22]);
+
+        intervals.insert(5..5);
+        assert_eq!(intervals.ranges(), [2..4, 6..8, 10..12, 20..22]);
+        intervals.insert(14..16);
+        assert_eq!(intervals.ranges(), [2..4, 6..8, 10..12, 14..16, 20..22]);
+        intervals.insert(0..1);
+        assert_eq!(
+            intervals.ranges(),
+            [0..1, 2..4, 6..8, 10..12, 14..16, 20..22]
+        );
+        intervals.insert(4..6);
+        assert_eq!(intervals.ranges(), [0..1, 2..8, 10..12, 14..16, 20..22]);
+        intervals.insert(11..21);
+        assert_eq!(intervals.ranges(), [0..1, 2..8, 10..22]);
+        intervals.insert(30..31);
+        assert_eq!(intervals.ranges(), [0..1, 2..8, 10..22, 30..31]);
+
+        // inserting the ranges one at a time makes the same set as collecting them
+        let ranges = [5..8, 0..2, 2..3, 7..10, 4..4, 12..15, 1..13, 20..21];
+        for n in 0..ranges.len() {
+            let mut inserted = IntervalSet::new();
+            for range in ranges[n..].iter().chain(&ranges[..n]) {
+                inserted.insert(range.clone());
+            }
+
+            assert_eq!(inserted, set(&ranges));
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let intervals = set(&[0..3, 5..10]);
+
+        assert!(intervals.contains(&0));
+        assert!(intervals.contains(&9));
+        assert!(!intervals.contains(&3));
+        assert!(!intervals.contains(&10));
+        assert!(!IntervalSet::from(2..5).is_subset(&intervals));
+        assert!(set(&[1..3, 6..8]).is_subset(&intervals));
+        assert!(intervals.is_superset(&IntervalSet::from(5..10)));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..5, 10..15, 20..25]);
+        let b = set(&[3..12, 14..21, 30..31]);
+
+        assert_eq!(a.union(&b).ranges(), [0..25, 30..31]);
+        assert_eq!(a.intersection(&b).ranges(), [3..5, 10..12, 14..15, 20..21]);
+        assert_eq!(a.difference(&b).ranges(), [0..3, 12..14, 21..25]);
+        assert_eq!(b.difference(&a).ranges(), [5..10, 15..20, 30..31]);
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&set(&[5..10, 25..30])));
+    }
+
+    #[test]
+    fn test_shift() {
+        let intervals = set(&[0..10, 20..30]);
+
+        assert_eq!(intervals.shift(5..25, 100).ranges(), [100..105, 115..120]);
+        assert_eq!(intervals.shift(10..20, 0).ranges(), []);
+    }
+
+    /// The operations agree with those of sets holding every number.
+    #[test]
+    fn test_against_every_number() {
+        use std::collections::BTreeSet;
+
+        let sets = [
+            set(&[]),
+            IntervalSet::from(0..4),
+            set(&[1..3, 5..9]),
+            set(&[2..6, 8..10]),
+            set(&[0..1, 3..4, 9..12]),
+        ];
+        let numbers = |s: &IntervalSet<u32>| {
+            s.ranges()
+                .iter()
+                .flat_map(|r| r.clone())
+                .collect::<BTreeSet<_>>()
+        };
+
+        for a in &sets {
+            for b in &sets {
+                let (x, y) = (numbers(a), numbers(b));
+
+                assert_eq!(numbers(&a.union(b)), &x | &y);
+                assert_eq!(numbers(&a.intersection(b)), &x & &y);
+                assert_eq!(numbers(&a.difference(b)), &x - &y);
+                assert_eq!(a.is_subset(b), x.is_subset(&y));
+                assert_eq!(a.overlaps(b), !x.is_disjoint(&y));
+            }
+        }
+    }
+}
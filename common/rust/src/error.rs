@@ -0,0 +1,158 @@
+use std::fmt::Display;
+
+use nom::error::ErrorKind;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AocError {
+    /// The input does not follow the puzzle format. Lines and columns start at 1.
+    Parse {
+        line: usize,
+        column: usize,
+        kind: ErrorKind,
+    },
+    /// The input follows the format but breaks one of the puzzle rules.
+    Invalid(String),
+    /// The input is valid but has no answer.
+    NoSolution(String),
+    /// The input could not be read.
+    Io(String),
+}
+
+impl AocError {
+    /// Locates `remaining`, a slice of `input` where parsing stopped, as a line and column
+    /// of `input`.
+    pub fn parse(input: &str, remaining: &str, kind: ErrorKind) -> Self {
+        let offset = (remaining.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| *offset <= input.len())
+            .unwrap_or(input.len() - remaining.len().min(input.len()));
+
+        let consumed = &input[..offset];
+        let line_start = consumed.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        AocError::Parse {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            kind,
+        }
+    }
+
+    pub fn from_nom(input: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => AocError::parse(input, e.input, e.code),
+            nom::Err::Incomplete(_) => {
+                AocError::parse(input, &input[input.len()..], ErrorKind::Eof)
+            }
+        }
+    }
+
+    /// Moves a parse error found in a single line to the line it came from.
+    pub fn on_line(self, line: usize) -> Self {
+        match self {
+            AocError::Parse { column, kind, .. } => AocError::Parse { line, column, kind },
+            error => error,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Parse { line, column, kind } => write!(
+                f,
+                "could not parse line {}, column {}: {}",
+                line,
+                column,
+                kind.description()
+            ),
+            AocError::Invalid(message) => write!(f, "invalid input: {}", message),
+            AocError::NoSolution(message) => write!(f, "no solution: {}", message),
+            AocError::Io(message) => write!(f, "could not read the input: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<std::io::Error> for AocError {
+    fn from(error: std::io::Error) -> Self {
+        AocError::Io(error.to_string())
+    }
+}
+
+/// Runs `parser` over the whole `input`, which may only be followed by trailing whitespace.
+pub fn parse_all<'a, O, P>(input: &'a str, mut parser: P) -> Result<O, AocError>
+where
+    P: FnMut(&'a str) -> nom::IResult<&'a str, O>,
+{
+    let (remaining, output) = parser(input).map_err(|e| AocError::from_nom(input, e))?;
+
+    if !remaining.trim().is_empty() {
+        let remaining = remaining.trim_start();
+        return Err(AocError::parse(input, remaining, ErrorKind::Eof));
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_location() {
+        let input = "abc\ndef\nghi";
+
+        assert_eq!(
+            AocError::parse(input, &input[5..], ErrorKind::Tag),
+            AocError::Parse {
+                line: 2,
+                column: 2,
+                kind: ErrorKind::Tag
+            }
+        );
+        assert_eq!(
+            AocError::parse(input, &input[0..], ErrorKind::Tag),
+            AocError::Parse {
+                line: 1,
+                column: 1,
+                kind: ErrorKind::Tag
+            }
+        );
+        assert_eq!(
+            AocError::parse(input, &input[11..], ErrorKind::Eof),
+            AocError::Parse {
+                line: 3,
+                column: 4,
+                kind: ErrorKind::Eof
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_all_rejects_trailing_input() {
+        let input = "12\n34\nxy";
+        let result = parse_all(input, nom::character::complete::u32);
+
+        assert_eq!(
+            result,
+            Err(AocError::Parse {
+                line: 2,
+                column: 1,
+                kind: ErrorKind::Eof
+            })
+        );
+        assert_eq!(parse_all("12\n", nom::character::complete::u32), Ok(12));
+    }
+
+    #[test]
+    fn test_display() {
+        let error = AocError::Parse {
+            line: 3,
+            column: 7,
+            kind: ErrorKind::Digit,
+        };
+
+        assert_eq!(error.to_string(), "could not parse line 3, column 7: Digit");
+    }
+}
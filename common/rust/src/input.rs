@@ -0,0 +1,88 @@
+use std::io::BufRead;
+
+use crate::error::AocError;
+
+/// Reads `reader` one line at a time, calling `f` with the number of every line, starting at
+/// 1, and its contents without the line ending. Only the current line is kept in memory, so
+/// inputs of any size can be read.
+pub fn for_each_line<R, F>(mut reader: R, mut f: F) -> Result<(), AocError>
+where
+    R: BufRead,
+    F: FnMut(usize, &str) -> Result<(), AocError>,
+{
+    let mut buffer = String::new();
+    let mut line = 0;
+
+    loop {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            return Ok(());
+        }
+
+        line += 1;
+        let contents = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        let contents = contents.strip_suffix('\r').unwrap_or(contents);
+
+        f(line, contents)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+
+    use super::*;
+
+    #[test]
+    fn test_for_each_line() {
+        let mut lines = vec![];
+        for_each_line("a\r\nbc\n\nd".as_bytes(), |i, line| {
+            lines.push((i, line.to_string()));
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(
+            lines,
+            vec![
+                (1, "a".to_string()),
+                (2, "bc".to_string()),
+                (3, "".to_string()),
+                (4, "d".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_for_each_line_stops_at_the_first_error() {
+        let mut read = 0;
+        let result = for_each_line("1\n2\n3\n".as_bytes(), |i, _| {
+            read += 1;
+            match i {
+                2 => Err(AocError::Invalid("line 2".to_string())),
+                _ => Ok(()),
+            }
+        });
+
+        assert_eq!(result, Err(AocError::Invalid("line 2".to_string())));
+        assert_eq!(read, 2);
+    }
+
+    #[test]
+    fn test_for_each_line_reports_read_errors() {
+        let reader = io::BufReader::new("1\n".as_bytes().chain(BrokenReader));
+
+        assert!(matches!(
+            for_each_line(reader, |_, _| Ok(())),
+            Err(AocError::Io(_))
+        ));
+    }
+
+    struct BrokenReader;
+
+    impl Read for BrokenReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! What the solutions of every year share: the error they report, the `Solution` trait
+//! the runner uses to parse and solve any day, and line by line reading of inputs too large
+//! to hold in memory.
+
+pub mod error;
+pub mod input;
+pub mod solution;
@@ -0,0 +1,64 @@
+use std::{fmt::Display, io::BufRead};
+
+use crate::error::AocError;
+
+/// The answer to one part of a puzzle. Days compute different number types, so the
+/// answer is only required to be printable.
+pub type Answer = Box<dyn Display>;
+
+/// A day of the calendar: the input is parsed once and both parts are answered from the
+/// parsed model.
+pub trait Solution {
+    fn parse(input: &str) -> Result<Self, AocError>
+    where
+        Self: Sized;
+
+    fn part_one(&self) -> Result<Answer, AocError>;
+
+    fn part_two(&self) -> Result<Answer, AocError>;
+
+    /// What looked wrong in the input without keeping it from being parsed.
+    fn warnings(&self) -> &[String] {
+        &[]
+    }
+}
+
+/// Parses an input into a type-erased solution, so days can be looked up at runtime.
+pub type Solver = fn(&str) -> Result<Box<dyn Solution>, AocError>;
+
+pub fn solver<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, AocError> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+/// A day whose parts can also be answered while reading the input one line at a time, in
+/// memory that does not grow with the input.
+pub trait Streaming {
+    fn stream_part_one(reader: &mut dyn BufRead) -> Result<Answer, AocError>;
+
+    fn stream_part_two(reader: &mut dyn BufRead) -> Result<Answer, AocError>;
+
+    /// Answers part 1 or 2 along with what looked wrong in the input without keeping it
+    /// from being read, which days that warn of nothing leave out.
+    fn stream_part(reader: &mut dyn BufRead, part: u8) -> Result<Streamed, AocError> {
+        let answer = match part {
+            1 => Self::stream_part_one(reader)?,
+            _ => Self::stream_part_two(reader)?,
+        };
+
+        Ok((answer, vec![]))
+    }
+}
+
+/// The answer to a part read one line at a time, with the warnings about its input.
+pub type Streamed = (Answer, Vec<String>);
+
+/// Answers one part of a streaming day from a reader, so those days can be looked up at
+/// runtime.
+pub type Streamer = fn(&mut dyn BufRead, u8) -> Result<Streamed, AocError>;
+
+pub fn streamer<S: Streaming>(reader: &mut dyn BufRead, part: u8) -> Result<Streamed, AocError> {
+    match part {
+        1 | 2 => S::stream_part(reader, part),
+        part => Err(AocError::Invalid(format!("there is no part {}", part))),
+    }
+}
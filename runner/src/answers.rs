@@ -0,0 +1,270 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use nom::{
+    bytes::complete::{is_not, take_till1},
+    character::complete::{space1, u16, u8},
+    sequence::{terminated, tuple},
+    IResult,
+};
+
+use aoc_common::error::{parse_all, AocError};
+
+use crate::{get_year_days, get_year_solver, get_year_streamer, YEARS};
+
+/// Identifies one expected answer: the part of a day of a year, solved on a given input.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AnswerKey {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// Path of the input, relative to the answers file.
+    pub input: String,
+}
+
+/// The expected answers of the puzzle inputs, as kept in the `answers.txt` file at the root
+/// of the repository: one `year day part input answer` entry per line, where `#` starts a
+/// comment line.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<AnswerKey, String>,
+}
+
+impl Answers {
+    /// Loads the answers file at `path`.
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| AocError::Invalid(format!("could not read {}: {}", path.display(), e)))?;
+
+        contents.parse()
+    }
+
+    /// The answers file committed at the root of the repository.
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.txt")
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8, input: &str) -> Option<&str> {
+        let key = AnswerKey {
+            year,
+            day,
+            part,
+            input: input.to_string(),
+        };
+
+        self.answers.get(&key).map(|answer| answer.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&AnswerKey, &str)> {
+        self.answers
+            .iter()
+            .map(|(key, answer)| (key, answer.as_str()))
+    }
+
+    /// The answers recorded for a year and day, in part order.
+    pub fn for_day(&self, year: u16, day: u8) -> impl Iterator<Item = (&AnswerKey, &str)> {
+        self.iter()
+            .filter(move |(key, _)| key.year == year && key.day == day)
+    }
+}
+
+impl FromStr for Answers {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = BTreeMap::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, answer) = parse_all(line, parse_answer).map_err(|e| e.on_line(i + 1))?;
+            if answers.insert(key.clone(), answer).is_some() {
+                return Err(AocError::Invalid(format!(
+                    "line {}: there already is an answer for {} day {} part {} on {}",
+                    i + 1,
+                    key.year,
+                    key.day,
+                    key.part,
+                    key.input
+                )));
+            }
+        }
+
+        Ok(Answers { answers })
+    }
+}
+
+fn parse_answer(line: &str) -> IResult<&str, (AnswerKey, String)> {
+    let (line, (year, day, part, input)) = tuple((
+        terminated(u16, space1),
+        terminated(u8, space1),
+        terminated(u8, space1),
+        terminated(take_till1(char::is_whitespace), space1),
+    ))(line)?;
+    let (line, answer) = is_not("\r\n")(line)?;
+
+    let key = AnswerKey {
+        year,
+        day,
+        part,
+        input: input.to_string(),
+    };
+
+    Ok((line, (key, answer.trim_end().to_string())))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Missing(String),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail(_) => write!(f, "fail"),
+            Status::Missing(_) => write!(f, "missing"),
+        }
+    }
+}
+
+/// The outcome of checking one part of a day against its expected answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<String>,
+    pub status: Status,
+}
+
+/// Runs every registered solver on the inputs it has answers for, streaming them too for the
+/// days that can be streamed, and reports every answer without a solver and every registered
+/// part without an answer as missing. Inputs are read relative to `root`.
+pub fn verify(answers: &Answers, root: &Path) -> Vec<Verification> {
+    let mut verifications = answers
+        .iter()
+        .map(|(key, expected)| Verification {
+            year: key.year,
+            day: key.day,
+            part: key.part,
+            input: Some(key.input.clone()),
+            status: check(key, expected, root),
+        })
+        .collect::<Vec<_>>();
+
+    for year in YEARS {
+        for day in get_year_days(year) {
+            for part in [1, 2] {
+                if answers.for_day(year, day).all(|(key, _)| key.part != part) {
+                    verifications.push(Verification {
+                        year,
+                        day,
+                        part,
+                        input: None,
+                        status: Status::Missing("no answer recorded".to_string()),
+                    });
+                }
+            }
+        }
+    }
+
+    verifications.sort_by_key(|v| (v.year, v.day, v.part));
+    verifications
+}
+
+fn check(key: &AnswerKey, expected: &str, root: &Path) -> Status {
+    let Some(solver) = get_year_solver(key.year, key.day) else {
+        return Status::Missing("no solver registered".to_string());
+    };
+
+    let path = root.join(&key.input);
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(e) => return Status::Fail(format!("could not read {}: {}", path.display(), e)),
+    };
+
+    let answer = solver(&input).and_then(|solution| match key.part {
+        1 => solution.part_one(),
+        2 => solution.part_two(),
+        part => Err(AocError::Invalid(format!("there is no part {}", part))),
+    });
+
+    match answer {
+        Ok(answer) if answer.to_string() == expected => {}
+        Ok(answer) => return Status::Fail(format!("expected {}, got {}", expected, answer)),
+        Err(e) => return Status::Fail(e.to_string()),
+    }
+
+    let Some(streamer) = get_year_streamer(key.year, key.day) else {
+        return Status::Pass;
+    };
+
+    match streamer(&mut input.as_bytes(), key.part).map(|(answer, _)| answer) {
+        Ok(answer) if answer.to_string() == expected => Status::Pass,
+        Ok(answer) => Status::Fail(format!("expected {}, streaming got {}", expected, answer)),
+        Err(e) => Status::Fail(format!("streaming: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::ErrorKind;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers =
+            "# comment\n\n2023 7 1 day_07/input.txt 6440\n2023 7 2 day_07/input.txt 5905\n"
+                .parse::<Answers>()
+                .unwrap();
+
+        assert_eq!(answers.get(2023, 7, 1, "day_07/input.txt"), Some("6440"));
+        assert_eq!(answers.get(2023, 7, 2, "day_07/input.txt"), Some("5905"));
+        assert_eq!(answers.get(2023, 7, 2, "day_07/other.txt"), None);
+        assert_eq!(answers.for_day(2023, 7).count(), 2);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "2023 7 1 input.txt 1\n2023 x 1 input.txt 2".parse::<Answers>(),
+            Err(AocError::Parse {
+                line: 2,
+                column: 6,
+                kind: ErrorKind::Digit
+            })
+        );
+        assert!(matches!(
+            "2023 7 1 input.txt 1\n2023 7 1 input.txt 2".parse::<Answers>(),
+            Err(AocError::Invalid(_))
+        ));
+    }
+
+    /// Every answer in the answers file must match what its solver computes, and every
+    /// registered day must have its answers recorded there.
+    #[test]
+    fn test_committed_answers() {
+        let path = Answers::default_path();
+        let answers = Answers::load(&path).unwrap();
+        let root = path.parent().unwrap();
+
+        let failures = verify(&answers, root)
+            .into_iter()
+            .filter(|v| v.status != Status::Pass)
+            .collect::<Vec<_>>();
+
+        assert_eq!(failures, vec![]);
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, Instant},
+};
+
+use aoc_2023_rust::{
+    day_03::{render, Format, GearRule, Schematic},
+    generate::write_input,
+};
+use aoc_runner::{
+    answers::{verify, Answers, Status},
+    get_year_days, get_year_solver, get_year_streamer, LATEST_YEAR,
+};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves one day on the given input
+    Run {
+        #[arg(short, long, default_value_t = LATEST_YEAR)]
+        year: u16,
+
+        #[arg(short, long)]
+        day: u8,
+
+        /// Part to solve; both parts are solved when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Path to the puzzle input, or `-` to read it from stdin
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Solve while reading the input one line at a time instead of loading it, for
+        /// inputs too large to fit in memory
+        #[arg(short, long)]
+        stream: bool,
+    },
+    /// Writes a random input of a 2023 day to stdout
+    Generate {
+        #[arg(short, long)]
+        day: u8,
+
+        /// Seed of the random numbers; the same seed always gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// How large the input is, with a meaning of its own for every day
+        #[arg(long, default_value_t = 10)]
+        size: usize,
+    },
+    /// Draws a 2023 day 3 schematic with its part numbers and gears highlighted
+    Render {
+        /// Path to the schematic, or `-` to read it from stdin
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Write HTML instead of colouring for a terminal
+        #[arg(long)]
+        html: bool,
+    },
+    /// Checks every registered solver against the expected answers
+    Verify {
+        /// Path to the answers file; inputs are read relative to its directory
+        #[arg(short, long, default_value_os_t = Answers::default_path())]
+        answers: PathBuf,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            stream: false,
+        } => run(year, day, part, &input),
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            stream: true,
+        } => run_streaming(year, day, part, &input),
+        Command::Generate { day, seed, size } => generate(day, seed, size),
+        Command::Render { input, html } => render_schematic(&input, html),
+        Command::Verify { answers } => verify_answers(&answers),
+    };
+
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+}
+
+fn run(year: u16, day: u8, part: Option<u8>, input: &Path) -> Result<(), String> {
+    let Some(solver) = get_year_solver(year, day) else {
+        return Err(format!(
+            "day {} of {} is not solved yet, available days are {:?}",
+            day,
+            year,
+            get_year_days(year)
+        ));
+    };
+
+    let input = read_input(input)?;
+
+    let start = Instant::now();
+    let solution = solver(&input).map_err(|e| e.to_string())?;
+    let parse_time = start.elapsed();
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    println!(
+        "{} day {} (parse: {})",
+        year,
+        day,
+        format_duration(parse_time)
+    );
+    print_warnings(solution.warnings());
+    for part in parts {
+        let start = Instant::now();
+        let answer = match part {
+            1 => solution.part_one(),
+            _ => solution.part_two(),
+        }
+        .map_err(|e| format!("part {}: {}", part, e))?;
+        let solve_time = start.elapsed();
+
+        println!(
+            "  part {}: {} (solve: {})",
+            part,
+            answer,
+            format_duration(solve_time)
+        );
+    }
+
+    Ok(())
+}
+
+fn run_streaming(year: u16, day: u8, part: Option<u8>, input: &Path) -> Result<(), String> {
+    let Some(streamer) = get_year_streamer(year, day) else {
+        return Err(format!(
+            "day {} of {} cannot be solved one line at a time",
+            day, year
+        ));
+    };
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None if input.as_os_str() == "-" => {
+            return Err("stdin can only be read once, choose a part to stream".to_string())
+        }
+        None => vec![1, 2],
+    };
+
+    println!("{} day {} (streaming)", year, day);
+    for part in parts {
+        let mut reader = open_input(input)?;
+
+        let start = Instant::now();
+        let (answer, warnings) =
+            streamer(&mut reader, part).map_err(|e| format!("part {}: {}", part, e))?;
+        let solve_time = start.elapsed();
+        print_warnings(&warnings);
+
+        println!(
+            "  part {}: {} (read and solve: {})",
+            part,
+            answer,
+            format_duration(solve_time)
+        );
+    }
+
+    Ok(())
+}
+
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+}
+
+fn generate(day: u8, seed: u64, size: usize) -> Result<(), String> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    write_input(day, seed, size, &mut out).map_err(|e| e.to_string())?;
+
+    out.flush()
+        .map_err(|e| format!("could not write the input: {}", e))
+}
+
+fn verify_answers(path: &Path) -> Result<(), String> {
+    let answers = Answers::load(path).map_err(|e| e.to_string())?;
+    let root = path.parent().unwrap_or(Path::new("."));
+
+    let verifications = verify(&answers, root);
+    let failures = verifications
+        .iter()
+        .filter(|v| matches!(v.status, Status::Fail(_)))
+        .count();
+
+    println!(
+        "{:<6} {:>3} {:>4}  {:<8} {:<45} details",
+        "year", "day", "part", "status", "input"
+    );
+    for v in verifications.iter() {
+        let details = match &v.status {
+            Status::Pass => "",
+            Status::Fail(details) | Status::Missing(details) => details,
+        };
+
+        println!(
+            "{:<6} {:>3} {:>4}  {:<8} {:<45} {}",
+            v.year,
+            v.day,
+            v.part,
+            v.status.to_string(),
+            v.input.as_deref().unwrap_or("-"),
+            details
+        );
+    }
+
+    if failures > 0 {
+        return Err(format!("{} answers are wrong", failures));
+    }
+
+    Ok(())
+}
+
+fn render_schematic(input: &Path, html: bool) -> Result<(), String> {
+    let input = read_input(input)?;
+    let schematic =
+        Schematic::new(&input.lines().collect::<Vec<_>>()).map_err(|e| e.to_string())?;
+    let format = match html {
+        true => Format::Html,
+        false => Format::Ansi,
+    };
+
+    print!("{}", render(&schematic, GearRule::puzzle(), format));
+
+    Ok(())
+}
+
+fn read_input(path: &Path) -> Result<String, String> {
+    if path.as_os_str() == "-" {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("could not read stdin: {}", e))?;
+
+        return Ok(input);
+    }
+
+    fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))
+}
+
+fn open_input(path: &Path) -> Result<Box<dyn BufRead>, String> {
+    if path.as_os_str() == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+
+    let file = File::open(path).map_err(|e| format!("could not open {}: {}", path.display(), e))?;
+
+    Ok(Box::new(BufReader::new(file)))
+}
+
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+
+    if micros < 1_000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.3}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.3}s", micros as f64 / 1_000_000.0)
+    }
+}
@@ -0,0 +1,59 @@
+pub mod answers;
+
+use aoc_common::solution::{Solver, Streamer};
+
+/// Every year with solutions in the workspace, oldest first.
+pub const YEARS: [u16; 3] = [
+    aoc_2021_rust::YEAR,
+    aoc_2022_rust::YEAR,
+    aoc_2023_rust::YEAR,
+];
+
+/// The year the runner solves when none is given.
+pub const LATEST_YEAR: u16 = aoc_2023_rust::YEAR;
+
+/// Looks a day up in the crate of its year.
+pub fn get_year_solver(year: u16, day: u8) -> Option<Solver> {
+    match year {
+        aoc_2021_rust::YEAR => aoc_2021_rust::get_solver(day),
+        aoc_2022_rust::YEAR => aoc_2022_rust::get_solver(day),
+        aoc_2023_rust::YEAR => aoc_2023_rust::get_solver(day),
+        _ => None,
+    }
+}
+
+/// Looks a day that can be solved one line at a time up in the crate of its year.
+pub fn get_year_streamer(year: u16, day: u8) -> Option<Streamer> {
+    match year {
+        aoc_2022_rust::YEAR => aoc_2022_rust::get_streamer(day),
+        aoc_2023_rust::YEAR => aoc_2023_rust::get_streamer(day),
+        _ => None,
+    }
+}
+
+pub fn get_year_days(year: u16) -> Vec<u8> {
+    (1..=25)
+        .filter(|day| get_year_solver(year, *day).is_some())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_year_days() {
+        assert_eq!(get_year_days(2021), vec![1, 2, 3]);
+        assert_eq!(get_year_days(2022), vec![1, 2, 3, 4, 5]);
+        assert_eq!(get_year_days(2023), aoc_2023_rust::get_days());
+        assert!(get_year_days(2020).is_empty());
+    }
+
+    #[test]
+    fn test_get_year_streamer() {
+        assert!(get_year_streamer(2022, 1).is_some());
+        assert!(get_year_streamer(2023, 9).is_some());
+        assert!(get_year_streamer(2023, 3).is_none());
+        assert!(get_year_streamer(2021, 1).is_none());
+    }
+}